@@ -1,21 +1,38 @@
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
 use rand::Rng;
 
-use seedcracker::{can_spawn_buried_treasure, check_seed};
+use seedcracker::observation::LootTarget;
+use seedcracker::pos::ChunkPos;
+use seedcracker::simd::{check_seeds, LANES};
+use seedcracker::{can_spawn_buried_treasure, check_seed, target_loot};
 
 pub fn criterion_benchmark(c: &mut Criterion) {
-    let mut rng = rand::thread_rng();
+    let mut rng = rand::rng();
+    let target = LootTarget::exact(&target_loot());
     c.bench_function("check_seed", |b| {
         b.iter_batched(
-            || (rng.gen::<u64>(), rng.gen::<i16>(), rng.gen::<i16>()),
-            |(seed, chunk_x, chunk_z)| check_seed(black_box(seed), chunk_x as i32, chunk_z as i32),
+            || (rng.random::<u64>(), rng.random::<i16>(), rng.random::<i16>()),
+            |(seed, chunk_x, chunk_z)| {
+                check_seed(
+                    black_box(seed),
+                    ChunkPos::new(chunk_x as i32, chunk_z as i32),
+                    &target,
+                )
+            },
+            BatchSize::SmallInput,
+        )
+    });
+    c.bench_function("check_seeds", |b| {
+        b.iter_batched(
+            || rng.random::<[u64; LANES]>(),
+            |seeds| black_box(check_seeds(black_box(&seeds), ChunkPos::new(-28, -73), &target)),
             BatchSize::SmallInput,
         )
     });
     c.bench_function("valid_spawn_position", |b| {
         b.iter_batched(
-            || rng.gen::<u64>(),
-            |seed| black_box(can_spawn_buried_treasure(seed, -28, -73)),
+            || rng.random::<u64>(),
+            |seed| black_box(can_spawn_buried_treasure(seed, ChunkPos::new(-28, -73))),
             BatchSize::SmallInput,
         )
     });
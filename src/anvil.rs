@@ -0,0 +1,309 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use flate2::read::{GzDecoder, ZlibDecoder};
+
+use crate::nbt::{binary, Tag};
+use crate::observation::{find_loot_table, ChestObservation, Container, LootTarget, Position};
+use crate::pos::BlockPos;
+use crate::random::mcversion::{
+    MCVersion, V1_10, V1_11, V1_12, V1_13, V1_14, V1_15, V1_16, V1_17, V1_18, V1_19, V1_20, V1_21,
+    V1_8, V1_9,
+};
+use crate::structure::Structure;
+
+const SECTOR_SIZE: usize = 4096;
+
+/// A region file holds 32 by 32 chunks.
+pub const REGION_CHUNKS: usize = 32;
+
+const GZIP: u8 = 1;
+const ZLIB: u8 = 2;
+const UNCOMPRESSED: u8 = 3;
+const LZ4: u8 = 4;
+/// Set on the compression type when the chunk was too big and lives in a `.mcc` file.
+const EXTERNAL: u8 = 128;
+
+/// The first `DataVersion` of each release a chunk may have been saved by, newest first.
+const DATA_VERSIONS: [(i64, MCVersion); 13] = [
+    (3953, V1_21),
+    (3463, V1_20),
+    (3105, V1_19),
+    (2860, V1_18),
+    (2724, V1_17),
+    (2566, V1_16),
+    (2225, V1_15),
+    (1952, V1_14),
+    (1519, V1_13),
+    (1139, V1_12),
+    (819, V1_11),
+    (510, V1_10),
+    (169, V1_9),
+];
+
+/// The release that saved a chunk with this `DataVersion`, counting its snapshots as the
+/// release before them. Chunks from before 1.9 have none.
+pub fn data_version_release(data_version: Option<i64>) -> MCVersion {
+    data_version
+        .and_then(|data_version| {
+            DATA_VERSIONS
+                .iter()
+                .find(|(first, _)| data_version >= *first)
+                .map(|(_, version)| *version)
+        })
+        .unwrap_or(V1_8)
+}
+
+/// The release a chunk was generated in, as far as the chunk can tell.
+///
+/// A chunk only records the `DataVersion` that last saved it, which is taken unless the
+/// chunk shows it was upgraded: 1.18 keeps `blending_data` or `below_zero_retrogen` on the
+/// chunks it converted from older worlds, and the release those were generated in is
+/// unknown.
+///
+/// An upgrade between releases before 1.18 leaves no such mark, so the chunks of a world
+/// that was opened in a newer release than it was created in still need the release
+/// given, see `find_chests`.
+pub fn generation_version(chunk: &Tag) -> Result<MCVersion, String> {
+    let saved_in = data_version_release(chunk.get("DataVersion").and_then(Tag::as_i64));
+    let level = chunk.get("Level").unwrap_or(chunk);
+    let upgraded = ["blending_data", "below_zero_retrogen"]
+        .iter()
+        .any(|key| level.get(key).is_some());
+    if saved_in.is_older_than(&V1_18) || !upgraded {
+        return Ok(saved_in);
+    }
+    let coordinate = |key| level.get(key).and_then(Tag::as_i64).unwrap_or_default();
+    Err(format!(
+        "chunk {} {} was generated before 1.18 and upgraded by {saved_in}, the release it was \
+         generated in has to be given",
+        coordinate("xPos"),
+        coordinate("zPos")
+    ))
+}
+
+/// An Anvil `.mca` file, read whole.
+#[derive(Debug, Clone)]
+pub struct RegionFile {
+    data: Vec<u8>,
+}
+
+impl RegionFile {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        Self::from_bytes(fs::read(path).map_err(|error| error.to_string())?)
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, String> {
+        if data.len() < 2 * SECTOR_SIZE {
+            return Err(format!(
+                "a region file starts with a {} byte header, this one has {} bytes",
+                2 * SECTOR_SIZE,
+                data.len()
+            ));
+        }
+        Ok(Self { data })
+    }
+
+    /// The NBT of chunk `(x, z)` within the region, `None` if it was never saved.
+    pub fn chunk(&self, x: usize, z: usize) -> Result<Option<Tag>, String> {
+        let entry = 4 * (x % REGION_CHUNKS + z % REGION_CHUNKS * REGION_CHUNKS);
+        let location = &self.data[entry..entry + 4];
+        let sector = u32::from_be_bytes([0, location[0], location[1], location[2]]) as usize;
+        if sector == 0 {
+            return Ok(None);
+        }
+        let error = |message: &str| format!("chunk {x} {z}: {message}");
+
+        let start = sector * SECTOR_SIZE;
+        let header = self
+            .data
+            .get(start..start + 5)
+            .ok_or_else(|| error("points past the end of the file"))?;
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let compression = header[4];
+        let payload = self
+            .data
+            .get(start + 5..start + 4 + length.max(1))
+            .ok_or_else(|| error("is cut off"))?;
+
+        let mut bytes = Vec::new();
+        let read = match compression {
+            GZIP => GzDecoder::new(payload).read_to_end(&mut bytes),
+            ZLIB => ZlibDecoder::new(payload).read_to_end(&mut bytes),
+            UNCOMPRESSED => {
+                bytes.extend_from_slice(payload);
+                Ok(bytes.len())
+            }
+            LZ4 => return Err(error("LZ4 compressed chunks are not supported")),
+            c if c & EXTERNAL != 0 => return Err(error("is stored in a separate .mcc file")),
+            c => return Err(error(&format!("unknown compression type {c}"))),
+        };
+        read.map_err(|e| error(&e.to_string()))?;
+        binary::read(&bytes)
+            .map(|(_, tag)| Some(tag))
+            .map_err(|e| error(&e))
+    }
+
+    /// Every saved chunk, row by row.
+    pub fn chunks(&self) -> impl Iterator<Item = Result<Tag, String>> + '_ {
+        (0..REGION_CHUNKS * REGION_CHUNKS)
+            .filter_map(|i| self.chunk(i % REGION_CHUNKS, i / REGION_CHUNKS).transpose())
+    }
+
+    /// The chests of every chunk that `find_chests` can turn into observations, and the
+    /// errors of the chunks that could not be read. LZ4 and `.mcc` chunks turn up in real
+    /// backups, so they are skipped rather than failing the whole region, and so are the
+    /// chunks whose generation release is unknown.
+    pub fn find_chests(
+        &self,
+        generated_in: Option<MCVersion>,
+    ) -> (Vec<ChestObservation>, Vec<String>) {
+        let mut chests = Vec::new();
+        let mut errors = Vec::new();
+        for chunk in self.chunks() {
+            match chunk {
+                Ok(chunk) => match find_chests(&chunk, generated_in) {
+                    Ok(found) => chests.extend(found),
+                    Err(error) => errors.push(error),
+                },
+                Err(error) => errors.push(error),
+            }
+        }
+        (chests, errors)
+    }
+}
+
+/// `[I; minX, minY, minZ, maxX, maxY, maxZ]`, inclusive.
+fn contains(bounds: &Tag, pos: BlockPos) -> bool {
+    match bounds {
+        Tag::IntArray(b) if b.len() == 6 => {
+            (b[0]..=b[3]).contains(&pos.x)
+                && (b[1]..=b[4]).contains(&pos.y)
+                && (b[2]..=b[5]).contains(&pos.z)
+        }
+        _ => false,
+    }
+}
+
+/// The bounding boxes of a buried treasure that starts in this chunk, which is where all
+/// of it is.
+fn buried_treasure_bounds(level: &Tag) -> Vec<&Tag> {
+    let start = ["structures", "Structures"]
+        .iter()
+        .find_map(|key| level.get(key))
+        .and_then(|structures| {
+            ["starts", "Starts"]
+                .iter()
+                .find_map(|key| structures.get(key))
+        })
+        .and_then(|starts| {
+            ["minecraft:buried_treasure", "buried_treasure"]
+                .iter()
+                .find_map(|key| starts.get(key))
+        });
+    let Some(start) = start else {
+        return Vec::new();
+    };
+    if start.get("id").and_then(Tag::as_str) == Some("INVALID") {
+        return Vec::new();
+    }
+    let children = start
+        .get("Children")
+        .and_then(Tag::as_list)
+        .unwrap_or_default();
+    start
+        .get("BB")
+        .into_iter()
+        .chain(children.iter().filter_map(|child| child.get("BB")))
+        .collect()
+}
+
+fn loot_table_structure(name: &str) -> Option<Structure> {
+    Structure::ALL
+        .into_iter()
+        .find(|&structure| find_loot_table(structure, name).is_some())
+}
+
+/// Observations for the chests of a chunk, as saved before and after 1.18:
+///
+/// - a chest that still has its `LootTable` was never opened, so it keeps the
+///   `LootTableSeed` the structure gave it, and its contents follow from it;
+/// - an opened chest inside a buried treasure is taken as it is.
+///
+/// Any other container says nothing about the seed and is skipped. Each chest is cracked
+/// with the seeding of `generated_in` when given, otherwise of the release the chunk was
+/// generated in as far as `generation_version` can tell.
+pub fn find_chests(
+    chunk: &Tag,
+    generated_in: Option<MCVersion>,
+) -> Result<Vec<ChestObservation>, String> {
+    let version = match generated_in {
+        Some(version) => version,
+        None => generation_version(chunk)?,
+    };
+    let level = chunk.get("Level").unwrap_or(chunk);
+    let bounds = buried_treasure_bounds(level);
+    let block_entities = ["block_entities", "TileEntities"]
+        .iter()
+        .find_map(|key| level.get(key))
+        .and_then(Tag::as_list)
+        .unwrap_or_default();
+
+    let mut chests = Vec::new();
+    for entity in block_entities {
+        let Some(container) = entity
+            .get("id")
+            .and_then(Tag::as_str)
+            .and_then(Container::from_id)
+        else {
+            continue;
+        };
+        let coordinate = |key| entity.get(key).and_then(Tag::as_i64).map(|v| v as i32);
+        let (Some(x), Some(y), Some(z)) = (coordinate("x"), coordinate("y"), coordinate("z"))
+        else {
+            continue;
+        };
+        let block = BlockPos::new(x, y, z);
+
+        let found = match entity.get("LootTable").and_then(Tag::as_str) {
+            Some(name) => {
+                let seed = entity.get("LootTableSeed").and_then(Tag::as_i64);
+                // A seed of zero is rolled when the chest is opened.
+                loot_table_structure(name)
+                    .zip(seed.filter(|&seed| seed != 0))
+                    .map(|(structure, seed)| {
+                        let table = find_loot_table(structure, name).unwrap();
+                        (
+                            structure,
+                            table,
+                            Some(seed),
+                            LootTarget::exact(&table.get_loot(seed, true)),
+                        )
+                    })
+            }
+            None if bounds.iter().any(|bounds| contains(bounds, block)) => entity
+                .get("Items")
+                .and_then(|items| LootTarget::from_items(items).ok())
+                .map(|target| {
+                    let structure = Structure::BuriedTreasure;
+                    (structure, &structure.loot_tables()[0], None, target)
+                }),
+            None => None,
+        };
+        if let Some((structure, loot_table, loot_seed, target)) = found {
+            chests.push(ChestObservation {
+                structure,
+                position: Position::Block(block),
+                container,
+                loot_table,
+                chest_index: 0,
+                start_chunk: None,
+                version,
+                loot_seed,
+                target,
+            });
+        }
+    }
+    Ok(chests)
+}
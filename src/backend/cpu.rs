@@ -0,0 +1,155 @@
+use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::thread;
+
+use crate::backend::scheduler::WorkQueue;
+use crate::backend::{Backend, ScanReporter, ScanTarget, TreasureTarget};
+use crate::check_seed;
+use crate::joint::{Clue, JointCracker};
+use crate::simd::{can_spawn_buried_treasure_seeds, check_seeds, LANES};
+
+/// Seeds a thread takes from the queue at once, and checks between two progress reports.
+const BLOCK_SIZE: u64 = 1 << 16;
+
+/// Checks seeds on CPU threads, `LANES` at a time. Threads take blocks of the range from
+/// a shared `WorkQueue` until it runs out or the reporter asks to stop.
+#[derive(Debug, Clone)]
+pub struct CpuBackend {
+    threads: usize,
+    target: Option<ScanTarget>,
+}
+
+impl CpuBackend {
+    pub const fn new(threads: usize) -> Self {
+        Self {
+            threads,
+            target: None,
+        }
+    }
+
+    pub const fn threads(&self) -> usize {
+        self.threads
+    }
+}
+
+impl Default for CpuBackend {
+    /// One thread per available core.
+    fn default() -> Self {
+        Self::new(thread::available_parallelism().map_or(1, NonZeroUsize::get))
+    }
+}
+
+/// Checks `block` `LANES` seeds at a time with `batch`, which sets bit `i` for a seed that
+/// may match, then confirms those with `check`. The seeds left over at the end only go
+/// through `check`.
+fn scan_lanes(
+    block: Range<u64>,
+    reporter: &dyn ScanReporter,
+    batch: impl Fn(&[u64; LANES]) -> u64,
+    check: impl Fn(u64) -> bool,
+) {
+    let mut seed = block.start;
+    while seed + LANES as u64 <= block.end {
+        let seeds: [u64; LANES] = std::array::from_fn(|i| seed + i as u64);
+        let mut mask = batch(&seeds);
+        while mask != 0 {
+            let seed = seeds[mask.trailing_zeros() as usize];
+            if check(seed) {
+                reporter.hit(seed);
+            }
+            mask &= mask - 1;
+        }
+        seed += LANES as u64;
+    }
+    for seed in seed..block.end {
+        if check(seed) {
+            reporter.hit(seed);
+        }
+    }
+}
+
+fn scan_block(block: Range<u64>, target: &TreasureTarget, reporter: &dyn ScanReporter) {
+    scan_lanes(
+        block,
+        reporter,
+        |seeds| check_seeds(seeds, target.chunk, &target.loot),
+        |seed| check_seed(seed, target.chunk, &target.loot),
+    );
+}
+
+/// A buried treasure driver filters its lanes in lockstep, any other clue is checked one
+/// seed at a time.
+fn scan_joint_block(block: Range<u64>, cracker: &JointCracker, reporter: &dyn ScanReporter) {
+    let check = |seed| cracker.check(seed);
+    match *cracker.driver() {
+        Clue::BuriedTreasure { chest, .. } => scan_lanes(
+            block,
+            reporter,
+            |seeds| can_spawn_buried_treasure_seeds(seeds, chest.chunk),
+            check,
+        ),
+        _ => scan_lanes(block, reporter, |_| u64::MAX, check),
+    }
+}
+
+impl Backend for CpuBackend {
+    fn name(&self) -> &'static str {
+        match self.target {
+            Some(ScanTarget::Joint(_)) => "joint-cpu",
+            _ => "cpu",
+        }
+    }
+
+    fn workers(&self) -> usize {
+        self.threads
+    }
+
+    fn prepare(&mut self, target: &ScanTarget) -> Result<(), String> {
+        if self.threads == 0 {
+            return Err("the CPU backend needs at least one thread".to_string());
+        }
+        self.target = Some(target.clone());
+        Ok(())
+    }
+
+    fn scan(&self, range: Range<u64>, reporter: &dyn ScanReporter) -> Result<u64, String> {
+        let target = self
+            .target
+            .as_ref()
+            .ok_or("the CPU backend was not prepared")?;
+        let queue = WorkQueue::new(range, BLOCK_SIZE);
+
+        let scanned = thread::scope(|scope| {
+            let mut workers = Vec::with_capacity(self.threads);
+            for worker in 0..self.threads {
+                let queue = &queue;
+                workers.push(scope.spawn(move || {
+                    let mut scanned = 0;
+                    while let Some(block) = queue.next_chunk() {
+                        if reporter.should_stop() {
+                            queue.cancel();
+                            break;
+                        }
+                        let seeds = block.end - block.start;
+                        match target {
+                            ScanTarget::BuriedTreasure(target) => {
+                                scan_block(block, target, reporter)
+                            }
+                            ScanTarget::Joint(cracker) => {
+                                scan_joint_block(block, cracker, reporter)
+                            }
+                        }
+                        reporter.progress(worker, seeds);
+                        scanned += seeds;
+                    }
+                    scanned
+                }));
+            }
+            workers
+                .into_iter()
+                .map(|worker| worker.join().expect("scan thread panicked"))
+                .sum()
+        });
+        Ok(scanned)
+    }
+}
@@ -0,0 +1,94 @@
+use std::ffi::c_void;
+use std::ops::Range;
+use std::slice;
+
+use metal::{Device, FunctionConstantValues, Library, MTLDataType, MTLResourceOptions, MTLSize};
+
+use crate::backend::{Backend, ScanReporter, ScanTarget, TreasureTarget};
+
+/// Built by `build.sh`, which needs `xcrun` and so only runs on macOS.
+const LIB_SOURCE: &[u8] = include_bytes!("../metal/find_seed.metallib");
+
+/// Runs the `find_seed` kernel on the default Metal device. The kernel checks the seeds
+/// from zero up to its `MAX_SEED` constant, one per thread, and writes a found flag and
+/// the seed into its result buffer.
+pub struct MetalBackend {
+    device: Device,
+    library: Option<Library>,
+}
+
+impl MetalBackend {
+    pub fn new() -> Result<Self, String> {
+        let device = Device::system_default().ok_or("no Metal device found")?;
+        Ok(Self {
+            device,
+            library: None,
+        })
+    }
+}
+
+impl Backend for MetalBackend {
+    fn name(&self) -> &'static str {
+        "metal"
+    }
+
+    /// The kernel has its chest position and loot compiled in, those of the default target.
+    fn prepare(&mut self, target: &ScanTarget) -> Result<(), String> {
+        let built_in = TreasureTarget::default();
+        if !matches!(target, ScanTarget::BuriedTreasure(target) if *target == built_in) {
+            return Err("the Metal kernel only checks the built-in target".to_string());
+        }
+        self.library = Some(self.device.new_library_with_data(LIB_SOURCE)?);
+        Ok(())
+    }
+
+    fn scan(&self, range: Range<u64>, reporter: &dyn ScanReporter) -> Result<u64, String> {
+        let library = self
+            .library
+            .as_ref()
+            .ok_or("the Metal backend was not prepared")?;
+        if range.start != 0 {
+            return Err("the Metal kernel can only scan ranges starting at zero".to_string());
+        }
+        let max_seed = range.end;
+
+        let constants = FunctionConstantValues::new();
+        constants.set_constant_value_at_index(
+            &max_seed as *const u64 as *const c_void,
+            MTLDataType::ULong,
+            0,
+        );
+        let function = library.get_function("find_seed", Some(constants))?;
+        let pipeline = self
+            .device
+            .new_compute_pipeline_state_with_function(&function)?;
+
+        let buffer_result = self.device.new_buffer(
+            size_of::<u64>() as u64 * 2,
+            MTLResourceOptions::StorageModeShared,
+        );
+
+        let command_queue = self.device.new_command_queue();
+        let command_buffer = command_queue.new_command_buffer();
+        let compute_encoder = command_buffer.new_compute_command_encoder();
+        compute_encoder.set_compute_pipeline_state(&pipeline);
+        compute_encoder.set_buffers(0, &[Some(&buffer_result)], &[0; 2]);
+
+        let max_threads_per_group = pipeline.max_total_threads_per_threadgroup();
+        let num_thread_groups = max_seed.div_ceil(max_threads_per_group);
+        compute_encoder.dispatch_threads(
+            MTLSize::new(num_thread_groups, 1, 1),
+            MTLSize::new(max_threads_per_group, 1, 1),
+        );
+        compute_encoder.end_encoding();
+        command_buffer.commit();
+        command_buffer.wait_until_completed();
+
+        let result = unsafe { slice::from_raw_parts(buffer_result.contents() as *const u64, 2) };
+        if result[0] != 0 {
+            reporter.hit(result[1]);
+        }
+        reporter.progress(0, max_seed);
+        Ok(max_seed)
+    }
+}
@@ -0,0 +1,119 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::joint::JointCracker;
+use crate::observation::LootTarget;
+use crate::pos::ChunkPos;
+use crate::target_loot;
+
+pub mod cpu;
+#[cfg(feature = "metal")]
+pub mod metal;
+pub mod progress;
+pub mod scheduler;
+
+/// The chest's target loot in the buried treasure of `chunk`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TreasureTarget {
+    pub chunk: ChunkPos,
+    pub loot: LootTarget,
+}
+
+impl TreasureTarget {
+    pub const fn new(chunk: ChunkPos, loot: LootTarget) -> Self {
+        Self { chunk, loot }
+    }
+}
+
+/// The built-in chest of `target_loot`, in the buried treasure of chunk -28, -73.
+impl Default for TreasureTarget {
+    fn default() -> Self {
+        Self::new(ChunkPos::new(-28, -73), LootTarget::exact(&target_loot()))
+    }
+}
+
+/// What a scan looks for.
+#[derive(Debug, Clone)]
+pub enum ScanTarget {
+    /// A single buried treasure chest, which backends check without any other clue.
+    BuriedTreasure(TreasureTarget),
+    /// Several chests at once, every seed goes through the clues of the cracker.
+    Joint(JointCracker),
+}
+
+impl ScanTarget {
+    /// Whether every chest was seen whole. A partly seen chest matches more seeds, and a
+    /// scan for it should not stop at the first.
+    pub fn is_exact(&self) -> bool {
+        match self {
+            Self::BuriedTreasure(target) => target.loot.is_exact(),
+            Self::Joint(cracker) => cracker.clues().iter().all(|clue| clue.target().is_exact()),
+        }
+    }
+}
+
+impl Default for ScanTarget {
+    fn default() -> Self {
+        Self::BuriedTreasure(TreasureTarget::default())
+    }
+}
+
+/// Receives what a backend finds while it scans. Backends may call it from several threads.
+pub trait ScanReporter: Sync {
+    fn hit(&self, seed: u64);
+
+    /// `worker` has checked `seeds` more seeds since its last report.
+    fn progress(&self, worker: usize, seeds: u64);
+
+    /// Asked between batches, a backend returns early once this is true.
+    fn should_stop(&self) -> bool {
+        false
+    }
+}
+
+/// Keeps every hit and counts the seeds checked, for scans that are only looked at once
+/// they are done.
+#[derive(Debug, Default)]
+pub struct CollectHits {
+    hits: Mutex<Vec<u64>>,
+    scanned: AtomicU64,
+}
+
+impl CollectHits {
+    pub fn scanned(&self) -> u64 {
+        self.scanned.load(Ordering::Relaxed)
+    }
+
+    /// The hits in the order they were reported.
+    pub fn into_hits(self) -> Vec<u64> {
+        self.hits.into_inner().unwrap()
+    }
+}
+
+impl ScanReporter for CollectHits {
+    fn hit(&self, seed: u64) {
+        self.hits.lock().unwrap().push(seed);
+    }
+
+    fn progress(&self, _worker: usize, seeds: u64) {
+        self.scanned.fetch_add(seeds, Ordering::Relaxed);
+    }
+}
+
+/// Something that can check a range of structure seeds against a target.
+pub trait Backend {
+    fn name(&self) -> &'static str;
+
+    /// How many workers report progress, numbered from zero.
+    fn workers(&self) -> usize {
+        1
+    }
+
+    /// Sets the backend up for `target`, must be called before `scan`.
+    fn prepare(&mut self, target: &ScanTarget) -> Result<(), String>;
+
+    /// Checks every seed of `range` unless the reporter stops it first, returning how many
+    /// seeds were checked.
+    fn scan(&self, range: Range<u64>, reporter: &dyn ScanReporter) -> Result<u64, String>;
+}
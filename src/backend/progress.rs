@@ -0,0 +1,136 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::format::format_num;
+
+/// A worker's seed count on its own cache line, so workers never contend on a counter.
+#[derive(Debug, Default)]
+#[repr(align(128))]
+struct WorkerCounter(AtomicU64);
+
+/// Counts what a scan has done so far, one counter per worker.
+#[derive(Debug)]
+pub struct ProgressTracker {
+    workers: Box<[WorkerCounter]>,
+    hits: AtomicU64,
+    total: u64,
+    started: Instant,
+}
+
+impl ProgressTracker {
+    /// Tracks a scan of `total` seeds by `workers` workers, starting now.
+    pub fn new(workers: usize, total: u64) -> Self {
+        Self {
+            workers: (0..workers.max(1))
+                .map(|_| WorkerCounter::default())
+                .collect(),
+            hits: AtomicU64::new(0),
+            total,
+            started: Instant::now(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn add_seeds(&self, worker: usize, seeds: u64) {
+        self.workers[worker % self.workers.len()]
+            .0
+            .fetch_add(seeds, Ordering::Relaxed);
+    }
+
+    pub fn add_hit(&self) {
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn scanned(&self) -> u64 {
+        self.workers
+            .iter()
+            .map(|counter| counter.0.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        ProgressSnapshot {
+            scanned: self.scanned(),
+            total: self.total,
+            hits: self.hits.load(Ordering::Relaxed),
+            elapsed: self.started.elapsed(),
+        }
+    }
+}
+
+/// The state of a scan at one point in time.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ProgressSnapshot {
+    pub scanned: u64,
+    pub total: u64,
+    pub hits: u64,
+    pub elapsed: Duration,
+}
+
+impl ProgressSnapshot {
+    /// Seeds per second since the scan started.
+    pub fn rate(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            0.0
+        } else {
+            self.scanned as f64 / seconds
+        }
+    }
+
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.scanned as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    /// Time left at the current rate, `None` until there is a rate to go by or while it is
+    /// too slow for the time left to fit a `Duration`.
+    pub fn eta(&self) -> Option<Duration> {
+        let rate = self.rate();
+        if rate == 0.0 {
+            return None;
+        }
+        let remaining = self.total.saturating_sub(self.scanned);
+        Duration::try_from_secs_f64(remaining as f64 / rate).ok()
+    }
+
+    /// One line of JSON, for tools following the scan.
+    pub fn to_json_line(&self) -> String {
+        let eta = self.eta().map_or_else(
+            || "null".to_string(),
+            |eta| format!("{:.3}", eta.as_secs_f64()),
+        );
+        format!(
+            "{{\"scanned\":{},\"total\":{},\"percent\":{:.4},\"rate\":{:.1},\"eta_secs\":{},\"hits\":{},\"elapsed_secs\":{:.3}}}",
+            self.scanned,
+            self.total,
+            self.percent(),
+            self.rate(),
+            eta,
+            self.hits,
+            self.elapsed.as_secs_f64()
+        )
+    }
+}
+
+impl fmt::Display for ProgressSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{} seeds ({:.2}%), {} seeds/s, ",
+            format_num(self.scanned),
+            format_num(self.total),
+            self.percent(),
+            format_num(self.rate() as u64)
+        )?;
+        match self.eta() {
+            Some(eta) => write!(f, "ETA {:.0?}", Duration::from_secs(eta.as_secs()))?,
+            None => write!(f, "ETA unknown")?,
+        }
+        write!(f, ", {} hits", self.hits)
+    }
+}
@@ -0,0 +1,48 @@
+use std::ops::Range;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Hands out consecutive chunks of a seed range to whichever worker asks next, so fast
+/// threads keep taking work while slow ones finish theirs.
+#[derive(Debug)]
+pub struct WorkQueue {
+    cursor: AtomicU64,
+    end: u64,
+    chunk_size: u64,
+    cancelled: AtomicBool,
+}
+
+impl WorkQueue {
+    pub const fn new(range: Range<u64>, chunk_size: u64) -> Self {
+        Self {
+            cursor: AtomicU64::new(range.start),
+            end: range.end,
+            chunk_size,
+            cancelled: AtomicBool::new(false),
+        }
+    }
+
+    /// The next chunk nobody has taken yet, `None` once the range is used up or the queue
+    /// is cancelled.
+    pub fn next_chunk(&self) -> Option<Range<u64>> {
+        if self.is_cancelled() {
+            return None;
+        }
+        // Never moves the cursor past the end, so it cannot wrap near `u64::MAX`.
+        let start = self
+            .cursor
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |start| {
+                (start < self.end).then(|| self.end.min(start.saturating_add(self.chunk_size)))
+            })
+            .ok()?;
+        Some(start..self.end.min(start.saturating_add(self.chunk_size)))
+    }
+
+    /// Stops handing out chunks. Workers finish the chunk they hold.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
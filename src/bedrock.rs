@@ -0,0 +1,147 @@
+use crate::pos::ChunkPos;
+use crate::random::chunkrand::ChunkRand;
+use crate::random::mcversion::{MCVersion, V1_13, V1_18};
+
+/// Highest layer the overworld bedrock floor can reach.
+pub const FLOOR_HEIGHT: usize = 5;
+
+/// Columns of a chunk's bedrock floor, one bit per layer set where there is bedrock.
+///
+/// Before 1.18 the floor is seeded from the chunk coordinates alone, so a pattern pins down
+/// where it was seen rather than the world seed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BedrockPattern {
+    columns: [u8; 256],
+}
+
+impl BedrockPattern {
+    /// The overworld floor of a chunk. Every column is assumed to take the single
+    /// `nextDouble` of the default surface builder first, which holds for most biomes.
+    pub fn generate(chunk: ChunkPos, version: &MCVersion) -> Option<Self> {
+        if version.is_newer_or_equal_to(&V1_18) {
+            return None;
+        }
+        let mut rand = ChunkRand::default().with_version(*version);
+        rand.set_terrain_seed(chunk);
+        let mut columns = [0; 256];
+
+        // Columns are stored z major, which is the order both versions fill them in.
+        if version.is_older_than(&V1_13) {
+            // `Biome.genBiomeTerrain` rolls a `nextInt(5)` for every height of the column and
+            // places the block with x and z swapped, so its x major loop fills the columns in
+            // z major order.
+            for column in &mut columns {
+                rand.get_next_double();
+                for y in (0..256).rev() {
+                    if y <= rand.get_next_int_bound(5) {
+                        *column |= 1 << y;
+                    }
+                }
+            }
+        } else {
+            for _ in 0..256 {
+                rand.get_next_double();
+            }
+            for column in &mut columns {
+                for y in (0..FLOOR_HEIGHT as i32).rev() {
+                    if y <= rand.get_next_int_bound(5) {
+                        *column |= 1 << y;
+                    }
+                }
+            }
+        }
+        Some(Self { columns })
+    }
+
+    #[inline(always)]
+    pub const fn is_bedrock(&self, x: usize, y: usize, z: usize) -> bool {
+        self.columns[z * 16 + x] >> y & 1 != 0
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BedrockCell {
+    pub x: u8,
+    pub y: u8,
+    pub z: u8,
+    pub is_bedrock: bool,
+}
+
+impl BedrockCell {
+    pub const fn new(x: u8, y: u8, z: u8, is_bedrock: bool) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            is_bedrock,
+        }
+    }
+}
+
+/// Reads one layer of a chunk, a line per z from north to south and a character per x
+/// from west to east: `#` is bedrock, `.` is anything else and `?` is unknown.
+pub fn parse_layer(y: u8, text: &str) -> Result<Vec<BedrockCell>, String> {
+    let mut cells = Vec::new();
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if lines.len() != 16 {
+        return Err(format!("expected 16 rows, got {}", lines.len()));
+    }
+    for (z, line) in lines.iter().enumerate() {
+        if line.chars().count() != 16 {
+            return Err(format!("row {z} should be 16 cells wide: `{line}`"));
+        }
+        for (x, c) in line.chars().enumerate() {
+            let is_bedrock = match c {
+                '#' => true,
+                '.' => false,
+                '?' => continue,
+                _ => return Err(format!("unknown cell `{c}` in row {z}")),
+            };
+            cells.push(BedrockCell::new(x as u8, y, z as u8, is_bedrock));
+        }
+    }
+    Ok(cells)
+}
+
+/// Locates an observed floor among the chunks of a world.
+///
+/// This cracks no world seed: before 1.18 the floor random is `setBaseChunkSeed(x, z)`,
+/// which never sees the world seed, so every world has the same floor in the same chunk.
+/// What a partial pattern gives away is where it was seen, and that chunk can feed the
+/// structure seed crackers that need one.
+pub struct BedrockCracker {
+    cells: Vec<BedrockCell>,
+    version: MCVersion,
+}
+
+impl BedrockCracker {
+    pub const fn new(cells: Vec<BedrockCell>, version: MCVersion) -> Self {
+        Self { cells, version }
+    }
+
+    pub fn observations(&self) -> &[BedrockCell] {
+        &self.cells
+    }
+
+    pub fn matches(&self, chunk: ChunkPos) -> bool {
+        BedrockPattern::generate(chunk, &self.version).is_some_and(|pattern| {
+            self.cells.iter().all(|cell| {
+                pattern.is_bedrock(cell.x as usize, cell.y as usize, cell.z as usize)
+                    == cell.is_bedrock
+            })
+        })
+    }
+
+    /// All chunks between the two corners, inclusive, whose floor agrees with every
+    /// observed cell.
+    pub fn find_chunks(&self, min: ChunkPos, max: ChunkPos) -> Vec<ChunkPos> {
+        (min.x..=max.x)
+            .flat_map(|x| (min.z..=max.z).map(move |z| ChunkPos::new(x, z)))
+            .filter(|&chunk| self.matches(chunk))
+            .collect()
+    }
+}
@@ -0,0 +1,263 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{self, BufRead, BufReader, ErrorKind, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::Range;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::backend::TreasureTarget;
+use crate::distributed::{Request, Response, WorkUnit};
+
+/// How long a worker is told to wait when every unit is out on lease.
+const WAIT: Duration = Duration::from_millis(200);
+
+/// Once the scan is done, a connection that has been quiet this long is closed. Workers
+/// told to wait ask again well before then and get `DONE` instead of a closed socket.
+const LINGER: Duration = Duration::from_secs(1);
+
+/// How often idle connections and the accept loop look at whether the scan is done.
+const POLL: Duration = Duration::from_millis(50);
+
+#[derive(Debug)]
+struct Lease {
+    unit: WorkUnit,
+    connection: u64,
+    deadline: Instant,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    pending: VecDeque<WorkUnit>,
+    leases: HashMap<u64, Lease>,
+    completed: HashSet<u64>,
+    units: u64,
+    scanned: u64,
+    /// Hits of units that are still leased, by `(connection, unit)`. They only count once
+    /// the unit completes, so a worker that drops out never reports a unit twice.
+    unconfirmed: HashMap<(u64, u64), Vec<u64>>,
+    hits: Vec<u64>,
+    next_connection: u64,
+}
+
+/// Splits a seed range into units and leases them to workers over TCP.
+///
+/// A unit goes back to the queue when its worker disconnects or holds it past the lease
+/// timeout, so a lost worker only costs the units it had.
+#[derive(Debug)]
+pub struct Coordinator {
+    target: TreasureTarget,
+    lease_timeout: Duration,
+    state: Mutex<State>,
+}
+
+impl Coordinator {
+    pub fn new(
+        target: TreasureTarget,
+        range: Range<u64>,
+        unit_size: u64,
+        lease_timeout: Duration,
+    ) -> Self {
+        let unit_size = unit_size.max(1);
+        let pending: VecDeque<WorkUnit> = range
+            .clone()
+            .step_by(unit_size as usize)
+            .enumerate()
+            .map(|(id, start)| WorkUnit {
+                id: id as u64,
+                range: start..range.end.min(start.saturating_add(unit_size)),
+            })
+            .collect();
+        Self {
+            target,
+            lease_timeout,
+            state: Mutex::new(State {
+                units: pending.len() as u64,
+                pending,
+                ..State::default()
+            }),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.completed.len() as u64 == state.units
+    }
+
+    /// The seeds found in completed units, in the order they were confirmed.
+    pub fn hits(&self) -> Vec<u64> {
+        self.state.lock().unwrap().hits.clone()
+    }
+
+    pub fn scanned(&self) -> u64 {
+        self.state.lock().unwrap().scanned
+    }
+
+    fn connect(&self) -> u64 {
+        let mut state = self.state.lock().unwrap();
+        state.next_connection += 1;
+        state.next_connection
+    }
+
+    fn lease(&self, connection: u64) -> Response {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        let expired: Vec<u64> = state
+            .leases
+            .iter()
+            .filter(|(_, lease)| lease.deadline <= now)
+            .map(|(&id, _)| id)
+            .collect();
+        for id in expired {
+            let lease = state.leases.remove(&id).unwrap();
+            state.unconfirmed.remove(&(lease.connection, id));
+            state.pending.push_back(lease.unit);
+        }
+
+        if state.completed.len() as u64 == state.units {
+            return Response::Done;
+        }
+        match state.pending.pop_front() {
+            Some(unit) => {
+                state.leases.insert(
+                    unit.id,
+                    Lease {
+                        unit: unit.clone(),
+                        connection,
+                        deadline: now + self.lease_timeout,
+                    },
+                );
+                drop(state);
+                Response::Unit(unit)
+            }
+            None => Response::Wait(WAIT),
+        }
+    }
+
+    fn hit(&self, connection: u64, unit: u64, seed: u64) -> Response {
+        let mut state = self.state.lock().unwrap();
+        match state.leases.get(&unit) {
+            Some(lease) if lease.connection == connection => {
+                if !lease.unit.range.contains(&seed) {
+                    return Response::Error(format!("seed {seed} is not in unit {unit}"));
+                }
+                state
+                    .unconfirmed
+                    .entry((connection, unit))
+                    .or_default()
+                    .push(seed);
+                drop(state);
+                Response::Ok
+            }
+            _ => Response::Error(format!("unit {unit} is not leased to this worker")),
+        }
+    }
+
+    fn complete(&self, connection: u64, unit: u64, scanned: u64) -> Response {
+        let mut state = self.state.lock().unwrap();
+        match state.leases.get(&unit) {
+            Some(lease) if lease.connection == connection => {}
+            _ => return Response::Error(format!("unit {unit} is not leased to this worker")),
+        }
+        state.leases.remove(&unit);
+        state.completed.insert(unit);
+        state.scanned += scanned;
+        if let Some(hits) = state.unconfirmed.remove(&(connection, unit)) {
+            state.hits.extend(hits);
+        }
+        Response::Ok
+    }
+
+    /// Puts every unit the connection still holds back in the queue.
+    fn disconnect(&self, connection: u64) {
+        let mut state = self.state.lock().unwrap();
+        let held: Vec<u64> = state
+            .leases
+            .iter()
+            .filter(|(_, lease)| lease.connection == connection)
+            .map(|(&id, _)| id)
+            .collect();
+        for id in held {
+            let lease = state.leases.remove(&id).unwrap();
+            state.pending.push_front(lease.unit);
+        }
+        state
+            .unconfirmed
+            .retain(|&(owner, _), _| owner != connection);
+    }
+
+    fn respond(&self, connection: u64, line: &str) -> Response {
+        match Request::parse(line) {
+            Ok(Request::Lease) => self.lease(connection),
+            Ok(Request::Hit { unit, seed }) => self.hit(connection, unit, seed),
+            Ok(Request::Complete { unit, scanned }) => self.complete(connection, unit, scanned),
+            Err(message) => Response::Error(message),
+        }
+    }
+
+    fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        let connection = self.connect();
+        let result = self.serve_connection(connection, stream);
+        self.disconnect(connection);
+        result
+    }
+
+    fn serve_connection(&self, connection: u64, stream: TcpStream) -> io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(POLL))?;
+        let mut writer = stream.try_clone()?;
+        let mut reader = BufReader::new(stream);
+        writeln!(
+            writer,
+            "{}",
+            Response::Hello {
+                target: self.target
+            }
+            .to_line()
+        )?;
+
+        let mut line = String::new();
+        let mut last_request = Instant::now();
+        loop {
+            match reader.read_line(&mut line) {
+                Ok(0) => return Ok(()),
+                Ok(_) => {
+                    let response = self.respond(connection, line.trim());
+                    writeln!(writer, "{}", response.to_line())?;
+                    line.clear();
+                    last_request = Instant::now();
+                }
+                // A partial line stays in `line` and is finished by the next read.
+                Err(error)
+                    if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                {
+                    if self.is_done() && last_request.elapsed() >= LINGER {
+                        return Ok(());
+                    }
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Accepts workers until every unit is complete.
+    pub fn serve(&self, listener: TcpListener) -> io::Result<()> {
+        listener.set_nonblocking(true)?;
+        thread::scope(|scope| {
+            while !self.is_done() {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        scope.spawn(move || {
+                            // A broken connection only loses its leases, which `handle`
+                            // already gave back.
+                            let _ = self.handle(stream);
+                        });
+                    }
+                    Err(error) if error.kind() == ErrorKind::WouldBlock => thread::sleep(POLL),
+                    Err(error) => return Err(error),
+                }
+            }
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,162 @@
+use std::ops::Range;
+use std::time::Duration;
+
+use crate::backend::TreasureTarget;
+use crate::observation::{parse_slot, LootTarget, SLOTS};
+use crate::pos::ChunkPos;
+use crate::ItemStack;
+
+pub mod coordinator;
+pub mod worker;
+
+/// Sent by the coordinator when a worker connects; both sides must agree on it.
+pub const PROTOCOL: &str = "seedcracker/2";
+
+/// A slice of the seed range leased out as a whole.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkUnit {
+    pub id: u64,
+    pub range: Range<u64>,
+}
+
+/// A line from a worker to the coordinator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    /// Asks for a unit to scan.
+    Lease,
+    /// A seed of a leased unit matched.
+    Hit { unit: u64, seed: u64 },
+    /// A leased unit has been scanned completely.
+    Complete { unit: u64, scanned: u64 },
+}
+
+/// A line from the coordinator to a worker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    /// The target, its chunk followed by one field per slot.
+    Hello {
+        target: TreasureTarget,
+    },
+    Unit(WorkUnit),
+    /// Every unit is leased but some are not done yet, ask again later.
+    Wait(Duration),
+    /// The whole range is done.
+    Done,
+    Ok,
+    Error(String),
+}
+
+/// A slot of the target as one field: `?`, `empty` or `minecraft:diamond*2`.
+fn slot_field(slot: &Option<ItemStack>) -> String {
+    match slot {
+        None => "?".to_string(),
+        Some(stack) if stack.is_empty() => "empty".to_string(),
+        Some(stack) => format!("{}*{}", stack.get_item().id(), stack.get_count()),
+    }
+}
+
+fn parse_slot_field(field: Option<&str>, line: &str) -> Result<Option<ItemStack>, String> {
+    let field = field.ok_or_else(|| format!("malformed line `{line}`"))?;
+    let slot = match field.split_once('*') {
+        Some((id, count)) => format!("{id} x{count}"),
+        None => field.to_string(),
+    };
+    parse_slot(&slot).map_err(|error| format!("{error} in `{line}`"))
+}
+
+fn parse_field<T: std::str::FromStr>(field: Option<&str>, line: &str) -> Result<T, String> {
+    field
+        .and_then(|field| field.parse().ok())
+        .ok_or_else(|| format!("malformed line `{line}`"))
+}
+
+impl Request {
+    pub fn to_line(&self) -> String {
+        match self {
+            Self::Lease => "LEASE".to_string(),
+            Self::Hit { unit, seed } => format!("HIT {unit} {seed}"),
+            Self::Complete { unit, scanned } => format!("COMPLETE {unit} {scanned}"),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut fields = line.split_whitespace();
+        let request = match fields.next() {
+            Some("LEASE") => Self::Lease,
+            Some("HIT") => Self::Hit {
+                unit: parse_field(fields.next(), line)?,
+                seed: parse_field(fields.next(), line)?,
+            },
+            Some("COMPLETE") => Self::Complete {
+                unit: parse_field(fields.next(), line)?,
+                scanned: parse_field(fields.next(), line)?,
+            },
+            _ => return Err(format!("unknown request `{line}`")),
+        };
+        match fields.next() {
+            Some(_) => Err(format!("malformed line `{line}`")),
+            None => Ok(request),
+        }
+    }
+}
+
+impl Response {
+    pub fn to_line(&self) -> String {
+        match self {
+            Self::Hello { target } => {
+                let mut line = format!("HELLO {PROTOCOL} {} {}", target.chunk.x, target.chunk.z);
+                for slot in &target.loot.slots {
+                    line.push(' ');
+                    line.push_str(&slot_field(slot));
+                }
+                line
+            }
+            Self::Unit(unit) => format!("UNIT {} {} {}", unit.id, unit.range.start, unit.range.end),
+            Self::Wait(duration) => format!("WAIT {}", duration.as_millis()),
+            Self::Done => "DONE".to_string(),
+            Self::Ok => "OK".to_string(),
+            Self::Error(message) => format!("ERROR {message}"),
+        }
+    }
+
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let mut fields = line.split_whitespace();
+        let response = match fields.next() {
+            Some("HELLO") => {
+                let protocol = fields.next();
+                if protocol != Some(PROTOCOL) {
+                    return Err(format!("expected protocol {PROTOCOL}, got `{line}`"));
+                }
+                let x = parse_field(fields.next(), line)?;
+                let z = parse_field(fields.next(), line)?;
+                let mut loot = LootTarget::unknown();
+                for slot in 0..SLOTS {
+                    loot.slots[slot] = parse_slot_field(fields.next(), line)?;
+                }
+                Self::Hello {
+                    target: TreasureTarget::new(ChunkPos::new(x, z), loot),
+                }
+            }
+            Some("UNIT") => Self::Unit(WorkUnit {
+                id: parse_field(fields.next(), line)?,
+                range: parse_field(fields.next(), line)?..parse_field(fields.next(), line)?,
+            }),
+            Some("WAIT") => Self::Wait(Duration::from_millis(parse_field(fields.next(), line)?)),
+            Some("DONE") => Self::Done,
+            Some("OK") => Self::Ok,
+            Some("ERROR") => {
+                return Ok(Self::Error(
+                    line.trim_start()
+                        .trim_start_matches("ERROR")
+                        .trim()
+                        .to_string(),
+                ))
+            }
+            _ => return Err(format!("unknown response `{line}`")),
+        };
+        match fields.next() {
+            Some(_) => Err(format!("malformed line `{line}`")),
+            None => Ok(response),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::thread;
+
+use crate::backend::{Backend, CollectHits, ScanTarget};
+use crate::distributed::{Request, Response};
+
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Connection {
+    fn receive(&mut self) -> Result<Response, String> {
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => Err("the coordinator closed the connection".to_string()),
+            Ok(_) => Response::parse(line.trim()),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    fn send(&mut self, request: &Request) -> Result<Response, String> {
+        writeln!(self.writer, "{}", request.to_line()).map_err(|error| error.to_string())?;
+        self.receive()
+    }
+}
+
+/// Leases units from the coordinator at `addr` and scans them with `backend` until the
+/// coordinator has none left, returning how many units this worker completed.
+pub fn run_worker(addr: impl ToSocketAddrs, backend: &mut dyn Backend) -> Result<u64, String> {
+    let stream = TcpStream::connect(addr).map_err(|error| error.to_string())?;
+    let mut connection = Connection {
+        reader: BufReader::new(stream.try_clone().map_err(|error| error.to_string())?),
+        writer: stream,
+    };
+
+    match connection.receive()? {
+        Response::Hello { target } => backend.prepare(&ScanTarget::BuriedTreasure(target))?,
+        response => return Err(format!("expected a greeting, got `{}`", response.to_line())),
+    }
+
+    let mut completed = 0;
+    loop {
+        let unit = match connection.send(&Request::Lease)? {
+            Response::Unit(unit) => unit,
+            Response::Wait(duration) => {
+                thread::sleep(duration);
+                continue;
+            }
+            Response::Done => return Ok(completed),
+            response => return Err(format!("unexpected response `{}`", response.to_line())),
+        };
+
+        // A unit's hits are kept until it is done and sent just before completing it.
+        let hits = CollectHits::default();
+        let scanned = backend.scan(unit.range.clone(), &hits)?;
+        for seed in hits.into_hits() {
+            connection.send(&Request::Hit {
+                unit: unit.id,
+                seed,
+            })?;
+        }
+        // An error here means the lease ran out and the unit went to someone else, which
+        // is not a reason to stop working.
+        if connection.send(&Request::Complete {
+            unit: unit.id,
+            scanned,
+        })? == Response::Ok
+        {
+            completed += 1;
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::f64::consts::PI;
+use std::ops::Range;
+
+use crate::random::jrand::JRand;
+use crate::random::mth::MASK_16;
+
+pub const PILLAR_COUNT: usize = 10;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EndPillar {
+    pub center_x: i32,
+    pub center_z: i32,
+    pub height: i32,
+    pub radius: i32,
+    pub guarded: bool,
+}
+
+impl EndPillar {
+    /// Builds the pillar at position `index` around the main island from its shuffled size class.
+    // Keep Java's rounding, a fused multiply-add could move a center by a block.
+    #[allow(clippy::suboptimal_flops)]
+    pub fn new(index: usize, size: usize) -> Self {
+        let angle = 2.0 * (-PI + (PI / 10.0) * index as f64);
+        Self {
+            center_x: (42.0 * angle.cos()).floor() as i32,
+            center_z: (42.0 * angle.sin()).floor() as i32,
+            height: 76 + size as i32 * 3,
+            radius: 2 + size as i32 / 3,
+            guarded: size == 1 || size == 2,
+        }
+    }
+}
+
+/// The 16-bit seed the spikes are shuffled with, `new Random(worldSeed).nextLong() & 0xFFFF`.
+#[inline(always)]
+pub const fn get_pillar_seed(world_seed: i64) -> i64 {
+    JRand::new(world_seed).get_next_long() & MASK_16 as i64
+}
+
+pub fn get_pillar_sizes(pillar_seed: i64) -> [usize; PILLAR_COUNT] {
+    let mut sizes = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    JRand::new(pillar_seed).shuffle(&mut sizes);
+    sizes
+}
+
+pub fn get_pillars(world_seed: i64) -> [EndPillar; PILLAR_COUNT] {
+    let sizes = get_pillar_sizes(get_pillar_seed(world_seed));
+    std::array::from_fn(|index| EndPillar::new(index, sizes[index]))
+}
+
+/// Matches the observed pillar heights, listed in generation order starting from the
+/// pillar at +X and going towards +Z. Unknown heights are `None`.
+pub struct EndPillarCracker {
+    sizes: [Option<usize>; PILLAR_COUNT],
+}
+
+impl EndPillarCracker {
+    pub fn new(heights: [Option<i32>; PILLAR_COUNT]) -> Option<Self> {
+        let mut sizes = [None; PILLAR_COUNT];
+        for (size, height) in sizes.iter_mut().zip(heights) {
+            let Some(height) = height else {
+                continue;
+            };
+            if height < 76 || (height - 76) % 3 != 0 || height > 76 + 9 * 3 {
+                return None;
+            }
+            *size = Some((height - 76) as usize / 3);
+        }
+        Some(Self { sizes })
+    }
+
+    pub fn matches_pillar_seed(&self, pillar_seed: i64) -> bool {
+        let sizes = get_pillar_sizes(pillar_seed);
+        self.sizes
+            .iter()
+            .zip(sizes)
+            .all(|(expected, size)| expected.is_none_or(|expected| expected == size))
+    }
+
+    pub fn check(&self, world_seed: i64) -> bool {
+        self.matches_pillar_seed(get_pillar_seed(world_seed))
+    }
+
+    pub fn pillar_seeds(&self) -> Vec<i64> {
+        (0..=MASK_16 as i64)
+            .filter(|&pillar_seed| self.matches_pillar_seed(pillar_seed))
+            .collect()
+    }
+
+    /// Every lower 32 bits of a world seed that produce one of the matching pillar seeds.
+    pub fn low_bits_candidates(&self) -> Vec<i64> {
+        self.pillar_seeds()
+            .into_iter()
+            .flat_map(lift_pillar_seed)
+            .collect()
+    }
+
+    /// Lower 48 bits to feed into the structure seed scan, 2^16 per low bits candidate.
+    pub fn structure_seeds(&self) -> impl Iterator<Item = u64> {
+        self.structure_seeds_in(0..MASK_16 as u64 + 1)
+    }
+
+    /// The structure seeds whose bits 32..48 fall within `upper_bits`, so a scan over the
+    /// 2^16 upper values can be split up.
+    pub fn structure_seeds_in(&self, upper_bits: Range<u64>) -> impl Iterator<Item = u64> {
+        self.low_bits_candidates()
+            .into_iter()
+            .flat_map(move |low_bits| {
+                upper_bits
+                    .clone()
+                    .map(move |upper| (upper << 32) | low_bits as u64)
+            })
+    }
+}
+
+/// Lifts a pillar seed to the lower 32 bits of the world seeds producing it.
+///
+/// The pillar seed is bits 16..32 of the second LCG state, and bit `k` of that state only
+/// depends on the seed bits up to `k`. The low 16 bits are free, every following bit
+/// is fixed one at a time, so about 2^16 candidates come out per pillar seed.
+pub fn lift_pillar_seed(pillar_seed: i64) -> Vec<i64> {
+    let mut candidates: Vec<i64> = (0..=MASK_16 as i64).collect();
+    for bit in 16..32 {
+        let target = (pillar_seed >> (bit - 16)) & 1;
+        candidates = candidates
+            .into_iter()
+            .flat_map(|low_bits| [low_bits, low_bits | 1 << bit])
+            .filter(|&low_bits| {
+                let mut rand = JRand::new(low_bits);
+                rand.next_seed();
+                (rand.next_seed() >> bit) & 1 == target
+            })
+            .collect();
+    }
+    candidates
+}
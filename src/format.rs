@@ -0,0 +1,25 @@
+/// Short human readable count, like `12k`, `3.4M` or `56B`.
+pub fn format_num(num: u64) -> String {
+    if num < 10_000 {
+        format!("{}", num)
+    } else if num < 1_000_000 {
+        format!("{:.0}k", (num as f64) / 1000.0)
+    } else if num < 10_000_000 {
+        format!("{:.1}M", (num as f64) / (1000.0 * 1000.0))
+    } else if num < 1_000_000_000 {
+        format!("{:.0}M", (num as f64) / (1000.0 * 1000.0))
+    } else if num < 10_000_000_000 {
+        format!("{:.1}B", (num as f64) / (1000.0 * 1000.0 * 1000.0))
+    } else if num < 1_000_000_000_000 {
+        format!("{:.0}B", (num as f64) / (1000.0 * 1000.0 * 1000.0))
+    } else if num < 10_000_000_000_000 {
+        format!("{:.1}T", (num as f64) / (1000.0 * 1000.0 * 1000.0 * 1000.0))
+    } else if num < 1_000_000_000_000_000 {
+        format!("{:.0}T", (num as f64) / (1000.0 * 1000.0 * 1000.0 * 1000.0))
+    } else {
+        format!(
+            "{:.2}Q",
+            (num as f64) / (1000.0 * 1000.0 * 1000.0 * 1000.0 * 1000.0)
+        )
+    }
+}
@@ -0,0 +1,108 @@
+use crate::random::mcversion::{MCVersion, V1_15};
+use crate::random::seeds::StructureSeed;
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// SHA-256 of the 8 little-endian bytes of `value`, as Guava's `hashLong` computes it.
+/// The message always fits in a single padded block, so only the first two output
+/// words are produced.
+#[inline(always)]
+const fn sha256_long(value: i64) -> [u32; 2] {
+    let bytes = value.to_le_bytes();
+    let mut w = [0_u32; 64];
+    w[0] = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    w[1] = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    w[2] = 0x80000000;
+    w[15] = 64;
+
+    let mut i = 16;
+    while i < 64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+        i += 1;
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = H;
+    let mut i = 0;
+    while i < 64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+        i += 1;
+    }
+
+    [H[0].wrapping_add(a), H[1].wrapping_add(b)]
+}
+
+/// `BiomeManager.obfuscateSeed`: the hashed seed sent in the login and respawn packets.
+#[inline(always)]
+pub const fn hash_world_seed(world_seed: i64) -> i64 {
+    let [a, b] = sha256_long(world_seed);
+    let a = a.to_be_bytes();
+    let b = b.to_be_bytes();
+    i64::from_le_bytes([a[0], a[1], a[2], a[3], b[0], b[1], b[2], b[3]])
+}
+
+/// Servers only send a hashed seed from 1.15 onwards.
+pub const fn has_hashed_seed(version: &MCVersion) -> bool {
+    version.is_newer_or_equal_to(&V1_15)
+}
+
+pub const fn verify_world_seed(world_seed: i64, hashed_seed: i64, version: &MCVersion) -> bool {
+    has_hashed_seed(version) && hash_world_seed(world_seed) == hashed_seed
+}
+
+pub fn filter_world_seeds<'a>(
+    world_seeds: impl IntoIterator<Item = i64> + 'a,
+    hashed_seed: i64,
+    version: &'a MCVersion,
+) -> impl Iterator<Item = i64> + 'a {
+    world_seeds
+        .into_iter()
+        .filter(move |&world_seed| verify_world_seed(world_seed, hashed_seed, version))
+}
+
+/// Tries all 2^16 upper-bit completions of a structure seed against the hashed seed.
+pub fn find_world_seeds(structure_seed: u64, hashed_seed: i64, version: &MCVersion) -> Vec<i64> {
+    if !has_hashed_seed(version) {
+        return Vec::new();
+    }
+    filter_world_seeds(
+        StructureSeed::get_world_seeds(structure_seed as i64),
+        hashed_seed,
+        version,
+    )
+    .collect()
+}
@@ -11,11 +11,458 @@ pub enum Item {
     IronSword,
     CookedCod,
     CookedSalmon,
+    BeetrootSeeds,
+    Saddle,
+    IronHorseArmor,
+    GoldenHorseArmor,
+    DiamondHorseArmor,
+    DiamondSword,
+    DiamondBoots,
+    DiamondChestplate,
+    DiamondLeggings,
+    DiamondHelmet,
+    DiamondPickaxe,
+    DiamondShovel,
+    DiamondHoe,
+    IronBoots,
+    IronChestplate,
+    IronLeggings,
+    IronHelmet,
+    IronPickaxe,
+    IronShovel,
+    ChainmailChestplate,
+    Lead,
+    GoldenApple,
+    EnchantedGoldenApple,
+    MusicDisc13,
+    MusicDiscCat,
+    NameTag,
+    Book,
+    EnchantedBook,
+    Bread,
+    Wheat,
+    Bucket,
+    Redstone,
+    Coal,
+    MelonSeeds,
+    PumpkinSeeds,
+    Bone,
+    Gunpowder,
+    RottenFlesh,
+    String,
+    NetheriteIngot,
+    AncientDebris,
+    NetheriteScrap,
+    SpectralArrow,
+    GoldBlock,
+    IronBlock,
+    CryingObsidian,
+    Quartz,
+    GildedBlackstone,
+    MagmaCream,
+    Apple,
+    Obsidian,
+    OakSapling,
+    Stick,
+    Map,
+    Paper,
+    Compass,
+    ClayBall,
+    FlowerPot,
+    Stone,
+    StoneBricks,
+    YellowDye,
+    SmoothStone,
+    WhiteWool,
+    BlackWool,
+    GrayWool,
+    BrownWool,
+    LightGrayWool,
+    Shears,
+    Porkchop,
+    Beef,
+    Mutton,
+    Arrow,
+    Feather,
+    Egg,
+    Flint,
+    Cod,
+    Salmon,
+    WaterBucket,
+    Barrel,
+    WheatSeeds,
+    Leather,
+    LeatherHelmet,
+    LeatherLeggings,
+    LeatherBoots,
+    LapisLazuli,
+    GoldNugget,
+    Dandelion,
+    Poppy,
+    Potato,
+    Grass,
+    TallGrass,
+    AcaciaSapling,
+    Torch,
+    GreenDye,
+    Cactus,
+    DeadBush,
+    BlueIce,
+    SnowBlock,
+    BeetrootSoup,
+    Furnace,
+    Snowball,
+    IronNugget,
+    Fern,
+    LargeFern,
+    SweetBerries,
+    PumpkinPie,
+    SpruceSapling,
+    SpruceSign,
+    SpruceLog,
     Empty,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ItemKind {
+    Helmet,
+    Chestplate,
+    Leggings,
+    Boots,
+    Sword,
+    Digger,
+    Book,
+    Other,
+}
+
 impl Item {
+    pub const ALL: [Self; 121] = [
+        Self::HeartOfTheSea,
+        Self::IronIngot,
+        Self::GoldIngot,
+        Self::TNT,
+        Self::Emerald,
+        Self::Diamond,
+        Self::PrismarineCrystals,
+        Self::LeatherChestplate,
+        Self::IronSword,
+        Self::CookedCod,
+        Self::CookedSalmon,
+        Self::BeetrootSeeds,
+        Self::Saddle,
+        Self::IronHorseArmor,
+        Self::GoldenHorseArmor,
+        Self::DiamondHorseArmor,
+        Self::DiamondSword,
+        Self::DiamondBoots,
+        Self::DiamondChestplate,
+        Self::DiamondLeggings,
+        Self::DiamondHelmet,
+        Self::DiamondPickaxe,
+        Self::DiamondShovel,
+        Self::DiamondHoe,
+        Self::IronBoots,
+        Self::IronChestplate,
+        Self::IronLeggings,
+        Self::IronHelmet,
+        Self::IronPickaxe,
+        Self::IronShovel,
+        Self::ChainmailChestplate,
+        Self::Lead,
+        Self::GoldenApple,
+        Self::EnchantedGoldenApple,
+        Self::MusicDisc13,
+        Self::MusicDiscCat,
+        Self::NameTag,
+        Self::Book,
+        Self::EnchantedBook,
+        Self::Bread,
+        Self::Wheat,
+        Self::Bucket,
+        Self::Redstone,
+        Self::Coal,
+        Self::MelonSeeds,
+        Self::PumpkinSeeds,
+        Self::Bone,
+        Self::Gunpowder,
+        Self::RottenFlesh,
+        Self::String,
+        Self::NetheriteIngot,
+        Self::AncientDebris,
+        Self::NetheriteScrap,
+        Self::SpectralArrow,
+        Self::GoldBlock,
+        Self::IronBlock,
+        Self::CryingObsidian,
+        Self::Quartz,
+        Self::GildedBlackstone,
+        Self::MagmaCream,
+        Self::Apple,
+        Self::Obsidian,
+        Self::OakSapling,
+        Self::Stick,
+        Self::Map,
+        Self::Paper,
+        Self::Compass,
+        Self::ClayBall,
+        Self::FlowerPot,
+        Self::Stone,
+        Self::StoneBricks,
+        Self::YellowDye,
+        Self::SmoothStone,
+        Self::WhiteWool,
+        Self::BlackWool,
+        Self::GrayWool,
+        Self::BrownWool,
+        Self::LightGrayWool,
+        Self::Shears,
+        Self::Porkchop,
+        Self::Beef,
+        Self::Mutton,
+        Self::Arrow,
+        Self::Feather,
+        Self::Egg,
+        Self::Flint,
+        Self::Cod,
+        Self::Salmon,
+        Self::WaterBucket,
+        Self::Barrel,
+        Self::WheatSeeds,
+        Self::Leather,
+        Self::LeatherHelmet,
+        Self::LeatherLeggings,
+        Self::LeatherBoots,
+        Self::LapisLazuli,
+        Self::GoldNugget,
+        Self::Dandelion,
+        Self::Poppy,
+        Self::Potato,
+        Self::Grass,
+        Self::TallGrass,
+        Self::AcaciaSapling,
+        Self::Torch,
+        Self::GreenDye,
+        Self::Cactus,
+        Self::DeadBush,
+        Self::BlueIce,
+        Self::SnowBlock,
+        Self::BeetrootSoup,
+        Self::Furnace,
+        Self::Snowball,
+        Self::IronNugget,
+        Self::Fern,
+        Self::LargeFern,
+        Self::SweetBerries,
+        Self::PumpkinPie,
+        Self::SpruceSapling,
+        Self::SpruceSign,
+        Self::SpruceLog,
+        Self::Empty,
+    ];
+
     pub const fn equals(&self, other: &Self) -> bool {
         *self as isize == *other as isize
     }
-}
\ No newline at end of file
+
+    pub const fn kind(&self) -> ItemKind {
+        match self {
+            Self::DiamondHelmet | Self::IronHelmet | Self::LeatherHelmet => ItemKind::Helmet,
+            Self::LeatherChestplate
+            | Self::DiamondChestplate
+            | Self::IronChestplate
+            | Self::ChainmailChestplate => ItemKind::Chestplate,
+            Self::DiamondLeggings | Self::IronLeggings | Self::LeatherLeggings => {
+                ItemKind::Leggings
+            }
+            Self::DiamondBoots | Self::IronBoots | Self::LeatherBoots => ItemKind::Boots,
+            Self::IronSword | Self::DiamondSword => ItemKind::Sword,
+            Self::DiamondPickaxe
+            | Self::DiamondShovel
+            | Self::DiamondHoe
+            | Self::IronPickaxe
+            | Self::IronShovel => ItemKind::Digger,
+            Self::Book => ItemKind::Book,
+            _ => ItemKind::Other,
+        }
+    }
+
+    /// `Item.getEnchantmentValue`, zero for items that can't be enchanted.
+    pub const fn enchantment_value(&self) -> i32 {
+        match self {
+            Self::LeatherHelmet
+            | Self::LeatherChestplate
+            | Self::LeatherLeggings
+            | Self::LeatherBoots => 15,
+            Self::ChainmailChestplate => 12,
+            Self::IronHelmet | Self::IronChestplate | Self::IronLeggings | Self::IronBoots => 9,
+            Self::IronSword | Self::IronPickaxe | Self::IronShovel => 14,
+            Self::DiamondHelmet
+            | Self::DiamondChestplate
+            | Self::DiamondLeggings
+            | Self::DiamondBoots
+            | Self::DiamondSword
+            | Self::DiamondPickaxe
+            | Self::DiamondShovel
+            | Self::DiamondHoe => 10,
+            Self::Book => 1,
+            _ => 0,
+        }
+    }
+
+    /// The resource location, as in `/give` or chest NBT. Water breathing potions are a
+    /// plain `minecraft:potion` told apart by their NBT.
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Self::HeartOfTheSea => "minecraft:heart_of_the_sea",
+            Self::IronIngot => "minecraft:iron_ingot",
+            Self::GoldIngot => "minecraft:gold_ingot",
+            Self::TNT => "minecraft:tnt",
+            Self::Emerald => "minecraft:emerald",
+            Self::Diamond => "minecraft:diamond",
+            Self::PrismarineCrystals => "minecraft:prismarine_crystals",
+            Self::LeatherChestplate => "minecraft:leather_chestplate",
+            Self::IronSword => "minecraft:iron_sword",
+            Self::CookedCod => "minecraft:cooked_cod",
+            Self::CookedSalmon => "minecraft:cooked_salmon",
+            Self::BeetrootSeeds => "minecraft:beetroot_seeds",
+            Self::Saddle => "minecraft:saddle",
+            Self::IronHorseArmor => "minecraft:iron_horse_armor",
+            Self::GoldenHorseArmor => "minecraft:golden_horse_armor",
+            Self::DiamondHorseArmor => "minecraft:diamond_horse_armor",
+            Self::DiamondSword => "minecraft:diamond_sword",
+            Self::DiamondBoots => "minecraft:diamond_boots",
+            Self::DiamondChestplate => "minecraft:diamond_chestplate",
+            Self::DiamondLeggings => "minecraft:diamond_leggings",
+            Self::DiamondHelmet => "minecraft:diamond_helmet",
+            Self::DiamondPickaxe => "minecraft:diamond_pickaxe",
+            Self::DiamondShovel => "minecraft:diamond_shovel",
+            Self::DiamondHoe => "minecraft:diamond_hoe",
+            Self::IronBoots => "minecraft:iron_boots",
+            Self::IronChestplate => "minecraft:iron_chestplate",
+            Self::IronLeggings => "minecraft:iron_leggings",
+            Self::IronHelmet => "minecraft:iron_helmet",
+            Self::IronPickaxe => "minecraft:iron_pickaxe",
+            Self::IronShovel => "minecraft:iron_shovel",
+            Self::ChainmailChestplate => "minecraft:chainmail_chestplate",
+            Self::Lead => "minecraft:lead",
+            Self::GoldenApple => "minecraft:golden_apple",
+            Self::EnchantedGoldenApple => "minecraft:enchanted_golden_apple",
+            Self::MusicDisc13 => "minecraft:music_disc_13",
+            Self::MusicDiscCat => "minecraft:music_disc_cat",
+            Self::NameTag => "minecraft:name_tag",
+            Self::Book => "minecraft:book",
+            Self::EnchantedBook => "minecraft:enchanted_book",
+            Self::Bread => "minecraft:bread",
+            Self::Wheat => "minecraft:wheat",
+            Self::Bucket => "minecraft:bucket",
+            Self::Redstone => "minecraft:redstone",
+            Self::Coal => "minecraft:coal",
+            Self::MelonSeeds => "minecraft:melon_seeds",
+            Self::PumpkinSeeds => "minecraft:pumpkin_seeds",
+            Self::Bone => "minecraft:bone",
+            Self::Gunpowder => "minecraft:gunpowder",
+            Self::RottenFlesh => "minecraft:rotten_flesh",
+            Self::String => "minecraft:string",
+            Self::NetheriteIngot => "minecraft:netherite_ingot",
+            Self::AncientDebris => "minecraft:ancient_debris",
+            Self::NetheriteScrap => "minecraft:netherite_scrap",
+            Self::SpectralArrow => "minecraft:spectral_arrow",
+            Self::GoldBlock => "minecraft:gold_block",
+            Self::IronBlock => "minecraft:iron_block",
+            Self::CryingObsidian => "minecraft:crying_obsidian",
+            Self::Quartz => "minecraft:quartz",
+            Self::GildedBlackstone => "minecraft:gilded_blackstone",
+            Self::MagmaCream => "minecraft:magma_cream",
+            Self::Apple => "minecraft:apple",
+            Self::Obsidian => "minecraft:obsidian",
+            Self::OakSapling => "minecraft:oak_sapling",
+            Self::Stick => "minecraft:stick",
+            Self::Map => "minecraft:map",
+            Self::Paper => "minecraft:paper",
+            Self::Compass => "minecraft:compass",
+            Self::ClayBall => "minecraft:clay_ball",
+            Self::FlowerPot => "minecraft:flower_pot",
+            Self::Stone => "minecraft:stone",
+            Self::StoneBricks => "minecraft:stone_bricks",
+            Self::YellowDye => "minecraft:yellow_dye",
+            Self::SmoothStone => "minecraft:smooth_stone",
+            Self::WhiteWool => "minecraft:white_wool",
+            Self::BlackWool => "minecraft:black_wool",
+            Self::GrayWool => "minecraft:gray_wool",
+            Self::BrownWool => "minecraft:brown_wool",
+            Self::LightGrayWool => "minecraft:light_gray_wool",
+            Self::Shears => "minecraft:shears",
+            Self::Porkchop => "minecraft:porkchop",
+            Self::Beef => "minecraft:beef",
+            Self::Mutton => "minecraft:mutton",
+            Self::Arrow => "minecraft:arrow",
+            Self::Feather => "minecraft:feather",
+            Self::Egg => "minecraft:egg",
+            Self::Flint => "minecraft:flint",
+            Self::Cod => "minecraft:cod",
+            Self::Salmon => "minecraft:salmon",
+            Self::WaterBucket => "minecraft:water_bucket",
+            Self::Barrel => "minecraft:barrel",
+            Self::WheatSeeds => "minecraft:wheat_seeds",
+            Self::Leather => "minecraft:leather",
+            Self::LeatherHelmet => "minecraft:leather_helmet",
+            Self::LeatherLeggings => "minecraft:leather_leggings",
+            Self::LeatherBoots => "minecraft:leather_boots",
+            Self::LapisLazuli => "minecraft:lapis_lazuli",
+            Self::GoldNugget => "minecraft:gold_nugget",
+            Self::Dandelion => "minecraft:dandelion",
+            Self::Poppy => "minecraft:poppy",
+            Self::Potato => "minecraft:potato",
+            Self::Grass => "minecraft:grass",
+            Self::TallGrass => "minecraft:tall_grass",
+            Self::AcaciaSapling => "minecraft:acacia_sapling",
+            Self::Torch => "minecraft:torch",
+            Self::GreenDye => "minecraft:green_dye",
+            Self::Cactus => "minecraft:cactus",
+            Self::DeadBush => "minecraft:dead_bush",
+            Self::BlueIce => "minecraft:blue_ice",
+            Self::SnowBlock => "minecraft:snow_block",
+            Self::BeetrootSoup => "minecraft:beetroot_soup",
+            Self::Furnace => "minecraft:furnace",
+            Self::Snowball => "minecraft:snowball",
+            Self::IronNugget => "minecraft:iron_nugget",
+            Self::Fern => "minecraft:fern",
+            Self::LargeFern => "minecraft:large_fern",
+            Self::SweetBerries => "minecraft:sweet_berries",
+            Self::PumpkinPie => "minecraft:pumpkin_pie",
+            Self::SpruceSapling => "minecraft:spruce_sapling",
+            Self::SpruceSign => "minecraft:spruce_sign",
+            Self::SpruceLog => "minecraft:spruce_log",
+            Self::Empty => "minecraft:air",
+        }
+    }
+
+    /// The item with resource location `id`, the `minecraft:` namespace may be left out.
+    pub fn from_id(id: &str) -> Option<Self> {
+        let id = id.strip_prefix("minecraft:").unwrap_or(id);
+        Self::ALL
+            .into_iter()
+            .find(|item| item.id().strip_prefix("minecraft:") == Some(id))
+    }
+
+    pub const fn max_stack_size(&self) -> u8 {
+        match self.kind() {
+            ItemKind::Other => match self {
+                Self::Saddle
+                | Self::IronHorseArmor
+                | Self::GoldenHorseArmor
+                | Self::DiamondHorseArmor
+                | Self::MusicDisc13
+                | Self::MusicDiscCat
+                | Self::EnchantedBook
+                | Self::Shears
+                | Self::WaterBucket
+                | Self::BeetrootSoup => 1,
+                Self::Bucket | Self::Egg | Self::Snowball | Self::SpruceSign => 16,
+                _ => 64,
+            },
+            ItemKind::Book => 64,
+            _ => 1,
+        }
+    }
+}
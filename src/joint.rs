@@ -0,0 +1,138 @@
+use crate::can_spawn_buried_treasure;
+use crate::observation::LootTarget;
+use crate::random::mcversion::V1_18;
+use crate::random::mth::MASK_48;
+use crate::structure::StructureChest;
+use crate::village::VillageCracker;
+
+/// Seeds the cheap step of every clue is tried on to guess how selective it is.
+const SAMPLE_SEEDS: u64 = 1 << 14;
+
+/// Spreads the sample over the whole 48 bit range instead of its lowest bits.
+const SAMPLE_STRIDE: u64 = 0x9e37_79b9_7f4b;
+
+/// One observed chest, each an independent filter on the same structure seed.
+#[derive(Debug, Copy, Clone)]
+pub enum Clue {
+    BuriedTreasure {
+        chest: StructureChest,
+        target: LootTarget,
+    },
+    Village(VillageCracker),
+    /// A chest of any other structure, which has no cheap step in front of its loot.
+    Chest {
+        chest: StructureChest,
+        target: LootTarget,
+    },
+}
+
+impl Clue {
+    pub const fn chest(&self) -> &StructureChest {
+        match self {
+            Self::BuriedTreasure { chest, .. } | Self::Chest { chest, .. } => chest,
+            Self::Village(cracker) => &cracker.chest,
+        }
+    }
+
+    pub const fn target(&self) -> &LootTarget {
+        match self {
+            Self::BuriedTreasure { target, .. } | Self::Chest { target, .. } => target,
+            Self::Village(cracker) => &cracker.target,
+        }
+    }
+
+    /// Whether the structure can be there at all, without rolling any loot.
+    #[inline(always)]
+    pub fn can_spawn(&self, structure_seed: u64) -> bool {
+        match self {
+            Self::BuriedTreasure { chest, .. } => {
+                can_spawn_buried_treasure(structure_seed, chest.chunk)
+            }
+            Self::Village(cracker) => cracker.can_spawn(structure_seed),
+            Self::Chest { .. } => true,
+        }
+    }
+
+    /// Whether the chest holds the observed loot, assuming `can_spawn` passed.
+    #[inline(always)]
+    pub fn matches_loot(&self, structure_seed: u64) -> bool {
+        match self {
+            Self::BuriedTreasure { chest, target } | Self::Chest { chest, target } => {
+                target.matches(&chest.get_loot(structure_seed as i64, true))
+            }
+            Self::Village(cracker) => cracker.matches_loot(structure_seed),
+        }
+    }
+
+    pub fn check(&self, structure_seed: u64) -> bool {
+        self.can_spawn(structure_seed) && self.matches_loot(structure_seed)
+    }
+
+    /// The share of seeds that get past `can_spawn`, estimated on a fixed sample. The loot
+    /// check is about as expensive for every clue, so this is what tells them apart.
+    pub fn pass_rate(&self) -> f64 {
+        let passed = (0..SAMPLE_SEEDS)
+            .filter(|i| self.can_spawn(i.wrapping_mul(SAMPLE_STRIDE) & MASK_48 as u64))
+            .count();
+        passed as f64 / SAMPLE_SEEDS as f64
+    }
+}
+
+/// Cracks one structure seed from several chests at once.
+///
+/// The most selective clue drives the scan and the others are only checked on the seeds
+/// it lets through, which is far cheaper than scanning for each chest and intersecting
+/// the results.
+#[derive(Debug, Clone)]
+pub struct JointCracker {
+    /// Most selective first, the first one is the driver.
+    clues: Vec<Clue>,
+}
+
+impl JointCracker {
+    /// Orders the clues by pass rate; ties keep the order they were given in.
+    ///
+    /// Chests from 1.18 on are refused: their loot is rolled from a random seeded with the
+    /// whole 64 bit world seed, which a scan over 48 bit structure seeds cannot reach.
+    pub fn new(clues: Vec<Clue>) -> Result<Self, String> {
+        if clues.is_empty() {
+            return Err("a joint crack needs at least one clue".to_string());
+        }
+        if let Some(clue) = clues
+            .iter()
+            .find(|clue| clue.chest().version.is_newer_or_equal_to(&V1_18))
+        {
+            return Err(format!(
+                "a {} chest can not be cracked from its structure seed, its loot is seeded \
+                 with the full world seed from 1.18 on",
+                clue.chest().version
+            ));
+        }
+        let mut rated: Vec<(f64, Clue)> = clues
+            .into_iter()
+            .map(|clue| (clue.pass_rate(), clue))
+            .collect();
+        rated.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(Self {
+            clues: rated.into_iter().map(|(_, clue)| clue).collect(),
+        })
+    }
+
+    pub fn driver(&self) -> &Clue {
+        &self.clues[0]
+    }
+
+    pub fn clues(&self) -> &[Clue] {
+        &self.clues
+    }
+
+    /// Every cheap step runs before any loot is rolled, the driver's first.
+    #[inline(always)]
+    pub fn check(&self, structure_seed: u64) -> bool {
+        self.clues.iter().all(|clue| clue.can_spawn(structure_seed))
+            && self
+                .clues
+                .iter()
+                .all(|clue| clue.matches_loot(structure_seed))
+    }
+}
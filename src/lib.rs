@@ -1,4 +1,4 @@
-#![feature(const_trait_impl, derive_const)]
+#![feature(const_trait_impl, portable_simd)]
 #![allow(dead_code)]
 #![warn(clippy::nursery)]
 
@@ -6,29 +6,50 @@ use std::mem::MaybeUninit;
 
 use crate::item::Item;
 use crate::item::Item::*;
+use crate::observation::{LootTarget, SLOTS};
+use crate::pos::ChunkPos;
 use crate::random::chunkrand::ChunkRand;
 use crate::random::jrand::JRand;
 use crate::random::mcversion::{MCVersion, V1_16_5};
 use crate::random::mth::MASK_48;
+use crate::structure::Structure;
 use arrayvec::copy::ArrayVecCopy;
-use const_for::const_for;
 
 #[global_allocator]
 static ALLOCATOR: snmalloc_rs::SnMalloc = snmalloc_rs::SnMalloc;
 
+pub mod anvil;
+pub mod backend;
+pub mod bedrock;
+pub mod distributed;
+pub mod end_pillars;
+pub mod format;
+pub mod hashed_seed;
 pub mod item;
+pub mod joint;
+pub mod loot;
+pub mod nbt;
+pub mod observation;
+pub mod pos;
 pub mod random;
+pub mod report;
+pub mod simd;
+pub mod slime;
+pub mod structure;
+pub mod text_seed;
+pub mod village;
 
 pub type InventoryVec<T> = ArrayVecCopy<T, 27>;
 
-pub const MC_VERSION: MCVersion = V1_16_5;
+/// The version a `ChunkRand` seeds with unless it is given one.
+pub const DEFAULT_VERSION: MCVersion = V1_16_5;
 
 const BURIED_TREASURE_SALT: i64 = 10387320;
 
 #[inline(always)]
-pub const fn can_spawn_buried_treasure_readable(seed: u64, chunk_x: i32, chunk_z: i32) -> bool {
-    let seed = ((chunk_x as i64 * 341873128712
-        + chunk_z as i64 * 132897987541
+pub const fn can_spawn_buried_treasure_readable(seed: u64, chunk: ChunkPos) -> bool {
+    let seed = ((chunk.x as i64 * 341873128712
+        + chunk.z as i64 * 132897987541
         + seed as i64
         + BURIED_TREASURE_SALT)
         ^ 0x5deece66d)
@@ -37,9 +58,9 @@ pub const fn can_spawn_buried_treasure_readable(seed: u64, chunk_x: i32, chunk_z
 }
 
 #[inline(always)]
-pub const fn can_spawn_buried_treasure(seed: u64, chunk_x: i32, chunk_z: i32) -> bool {
-    (((((((chunk_x as i64 * 341873128712
-        + chunk_z as i64 * 132897987541
+pub const fn can_spawn_buried_treasure(seed: u64, chunk: ChunkPos) -> bool {
+    (((((((chunk.x as i64 * 341873128712
+        + chunk.z as i64 * 132897987541
         + seed as i64
         + 10387320)
         ^ 0x5deece66d)
@@ -51,23 +72,25 @@ pub const fn can_spawn_buried_treasure(seed: u64, chunk_x: i32, chunk_z: i32) ->
         < 167772.16
 }
 
+/// Whether the buried treasure of `chunk` is there and agrees with every known slot of
+/// `target`.
 #[must_use]
 #[inline(always)]
-pub fn check_seed(seed: u64, chunk_x: i32, chunk_z: i32) -> bool {
-    if !can_spawn_buried_treasure(seed, chunk_x, chunk_z) {
+pub fn check_seed(seed: u64, chunk: ChunkPos, target: &LootTarget) -> bool {
+    if !can_spawn_buried_treasure(seed, chunk) {
         return false;
     }
-    get_loot(
-        i64::from_be_bytes(seed.to_be_bytes()),
-        chunk_x,
-        chunk_z,
-        true,
-    )
-    .is_some_and(|x| x == TARGET_ITEMS)
+    get_loot(i64::from_be_bytes(seed.to_be_bytes()), chunk, target, true)
+        .is_some_and(|loot| target.matches(&loot))
+}
+
+/// The chest contents the scan looks for unless it is given observations, slot by slot.
+pub const fn target_loot() -> InventoryVec<ItemStack> {
+    TARGET_ITEMS
 }
 
 #[inline(always)]
-fn get_count(rand: &mut ChunkRand, min: i32, max: i32) -> i32 {
+const fn get_count(rand: &mut ChunkRand, min: i32, max: i32) -> i32 {
     if min >= max {
         min
     } else {
@@ -79,8 +102,17 @@ fn get_count(rand: &mut ChunkRand, min: i32, max: i32) -> i32 {
 pub struct ItemStack(Item, u8);
 
 impl ItemStack {
+    pub const fn new(item: Item, count: u8) -> Self {
+        Self(item, count)
+    }
+
+    #[inline(always)]
+    pub const fn get_item(&self) -> Item {
+        self.0
+    }
+
     #[inline(always)]
-    const fn get_count(&self) -> u8 {
+    pub const fn get_count(&self) -> u8 {
         self.1
     }
 
@@ -91,7 +123,7 @@ impl ItemStack {
         item_stack
     }
 
-    const fn is_empty(&self) -> bool {
+    pub const fn is_empty(&self) -> bool {
         (self.0 as isize) == (Empty as isize) || self.1 == 0
     }
 }
@@ -130,32 +162,6 @@ const TARGET_ITEMS: InventoryVec<ItemStack> = InventoryVec {
     ],
 };
 
-macro_rules! item_count {
-    ($item:expr) => {
-        {
-            let mut res = 0;
-            const_for!(i in (0..TARGET_ITEMS.len()) => {
-                let item_stack = unsafe { TARGET_ITEMS.xs[i].assume_init() };
-                if item_stack.0.equals(&$item) {
-                    res += item_stack.1;
-                }
-            });
-            res
-        }
-    };
-}
-
-const IRON_INGOT_COUNT: u8 = item_count!(IronIngot);
-const GOLD_INGOT_COUNT: u8 = item_count!(GoldIngot);
-const TNT_COUNT: u8 = item_count!(TNT);
-const EMERALD_COUNT: u8 = item_count!(Emerald);
-const DIAMOND_COUNT: u8 = item_count!(Diamond);
-const PRISMARINE_COUNT: u8 = item_count!(PrismarineCrystals);
-const CHESTPLATE_COUNT: u8 = item_count!(LeatherChestplate);
-const SWORD_COUNT: u8 = item_count!(IronSword);
-const COOKED_COD_COUNT: u8 = item_count!(CookedCod);
-const COOKED_SALMON_COUNT: u8 = item_count!(CookedSalmon);
-
 const EXIT_EARLY: bool = cfg!(feature = "exit-early");
 
 macro_rules! exit_function {
@@ -164,10 +170,15 @@ macro_rules! exit_function {
     };
 }
 
+/// With `exit-early`, gives up as soon as the loot can no longer hold the totals of a
+/// fully known `target`. A target with unknown slots is always rolled out.
 fn generate_buried_treasure_loot(
     mut rand: ChunkRand,
+    target: &LootTarget,
     indexed: bool,
 ) -> Option<InventoryVec<ItemStack>> {
+    let exact = EXIT_EARLY && target.known_slots() == SLOTS;
+    let total = |item| target.count(item).0 as u8;
     let mut loot = InventoryVec::new();
     loot.push(ItemStack(HeartOfTheSea, 1));
     let rolls = get_count(&mut rand, 5, 8);
@@ -177,21 +188,21 @@ fn generate_buried_treasure_loot(
     for _ in 0..rolls {
         let weight = rand.get_next_int_bound(35);
         if weight < 20 {
-            if EXIT_EARLY && IRON_INGOT_COUNT == 0 {
+            if exact && total(IronIngot) == 0 {
                 exit_function!();
             }
             let value = get_count(&mut rand, 1, 4) as u8;
             iron_ingot_count += value;
             loot.push(ItemStack(IronIngot, value));
         } else if weight < 30 {
-            if EXIT_EARLY && GOLD_INGOT_COUNT == 0 {
+            if exact && total(GoldIngot) == 0 {
                 exit_function!();
             }
             let value = get_count(&mut rand, 1, 4) as u8;
             gold_ingot_count += value;
             loot.push(ItemStack(GoldIngot, value));
         } else {
-            if EXIT_EARLY && TNT_COUNT == 0 {
+            if exact && total(TNT) == 0 {
                 exit_function!();
             }
             let value = get_count(&mut rand, 1, 2) as u8;
@@ -199,10 +210,10 @@ fn generate_buried_treasure_loot(
             loot.push(ItemStack(TNT, value));
         }
     }
-    if EXIT_EARLY
-        && (iron_ingot_count != IRON_INGOT_COUNT
-            || gold_ingot_count != GOLD_INGOT_COUNT
-            || tnt_count != TNT_COUNT)
+    if exact
+        && (iron_ingot_count != total(IronIngot)
+            || gold_ingot_count != total(GoldIngot)
+            || tnt_count != total(TNT))
     {
         exit_function!();
     }
@@ -213,21 +224,21 @@ fn generate_buried_treasure_loot(
     for _ in 0..rolls {
         let weight = rand.get_next_int_bound(15);
         if weight < 5 {
-            if EXIT_EARLY && EMERALD_COUNT == 0 {
+            if exact && total(Emerald) == 0 {
                 exit_function!();
             }
             let value = get_count(&mut rand, 4, 8) as u8;
             emerald_count += value;
             loot.push(ItemStack(Emerald, value));
         } else if weight < 10 {
-            if EXIT_EARLY && DIAMOND_COUNT == 0 {
+            if exact && total(Diamond) == 0 {
                 exit_function!();
             }
             let value = get_count(&mut rand, 1, 2) as u8;
             diamond_count += value;
             loot.push(ItemStack(Diamond, value));
         } else {
-            if EXIT_EARLY && PRISMARINE_COUNT == 0 {
+            if exact && total(PrismarineCrystals) == 0 {
                 exit_function!();
             }
             let value = get_count(&mut rand, 1, 5) as u8;
@@ -236,27 +247,27 @@ fn generate_buried_treasure_loot(
         }
     }
 
-    if EXIT_EARLY
-        && (emerald_count != EMERALD_COUNT
-            || diamond_count != DIAMOND_COUNT
-            || prismarine_count != PRISMARINE_COUNT)
+    if exact
+        && (emerald_count != total(Emerald)
+            || diamond_count != total(Diamond)
+            || prismarine_count != total(PrismarineCrystals))
     {
         exit_function!();
     }
 
     let should_roll = rand.get_next_bool();
-    if EXIT_EARLY && (CHESTPLATE_COUNT != 0 || SWORD_COUNT != 0) != should_roll {
+    if exact && (total(LeatherChestplate) != 0 || total(IronSword) != 0) != should_roll {
         exit_function!();
     }
     if should_roll {
         let weight = rand.get_next_int_bound(2);
         if weight < 1 {
-            if EXIT_EARLY && CHESTPLATE_COUNT == 0 {
+            if exact && total(LeatherChestplate) == 0 {
                 exit_function!();
             }
             loot.push(ItemStack(LeatherChestplate, 1))
         } else {
-            if EXIT_EARLY && SWORD_COUNT == 0 {
+            if exact && total(IronSword) == 0 {
                 exit_function!();
             }
             loot.push(ItemStack(IronSword, 1))
@@ -269,14 +280,14 @@ fn generate_buried_treasure_loot(
     for _ in 0..2 {
         let weight = rand.get_next_int_bound(2);
         if weight < 1 {
-            if EXIT_EARLY && COOKED_COD_COUNT == 0 {
+            if exact && total(CookedCod) == 0 {
                 exit_function!();
             }
             let value = get_count(&mut rand, 2, 4) as u8;
             cooked_cod_count += value;
             loot.push(ItemStack(CookedCod, value));
         } else {
-            if EXIT_EARLY && COOKED_SALMON_COUNT == 0 {
+            if exact && total(CookedSalmon) == 0 {
                 exit_function!();
             }
             let value = get_count(&mut rand, 2, 4) as u8;
@@ -285,8 +296,8 @@ fn generate_buried_treasure_loot(
         }
     }
 
-    if EXIT_EARLY
-        && (cooked_cod_count != COOKED_COD_COUNT || cooked_salmon_count != COOKED_SALMON_COUNT)
+    if exact
+        && (cooked_cod_count != total(CookedCod) || cooked_salmon_count != total(CookedSalmon))
     {
         exit_function!();
     }
@@ -300,7 +311,7 @@ fn generate_buried_treasure_loot(
     }
 }
 
-const DEFAULT_CONTAINER: InventoryVec<usize> = InventoryVec {
+pub(crate) const DEFAULT_CONTAINER: InventoryVec<usize> = InventoryVec {
     len: 27,
     xs: [
         MaybeUninit::new(0),
@@ -366,7 +377,7 @@ const DEFAULT_INVENTORY: InventoryVec<ItemStack> = InventoryVec {
     ],
 };
 
-fn shuffle_items(
+pub(crate) fn shuffle_items(
     rand: &mut ChunkRand,
     items: InventoryVec<ItemStack>,
     container: InventoryVec<usize>,
@@ -418,12 +429,16 @@ fn shuffle_items(
 
 fn get_loot(
     structure_seed: i64,
-    chunk_x: i32,
-    chunk_z: i32,
+    chunk: ChunkPos,
+    target: &LootTarget,
     indexed: bool,
 ) -> Option<InventoryVec<ItemStack>> {
     let mut rand = ChunkRand::default();
-    rand.set_decorator_seed_block_salt(structure_seed, chunk_x * 16, chunk_z * 16, 30001);
+    rand.set_decorator_seed_block_salt(
+        structure_seed,
+        chunk.get_min_block(),
+        Structure::BuriedTreasure.salt(),
+    );
     let loot_rand = ChunkRand::new(rand.get_next_long());
-    generate_buried_treasure_loot(loot_rand, indexed)
+    generate_buried_treasure_loot(loot_rand, target, indexed)
 }
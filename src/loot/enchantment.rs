@@ -0,0 +1,393 @@
+use crate::item::{Item, ItemKind};
+use crate::random::chunkrand::ChunkRand;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EnchantmentCategory {
+    Armor,
+    ArmorFeet,
+    ArmorLegs,
+    ArmorChest,
+    ArmorHead,
+    Weapon,
+    Digger,
+    FishingRod,
+    Trident,
+    Breakable,
+    Bow,
+    Wearable,
+    Crossbow,
+    Vanishable,
+}
+
+impl EnchantmentCategory {
+    pub const fn can_enchant(&self, item: Item) -> bool {
+        let kind = item.kind();
+        let is_armor = matches!(
+            kind,
+            ItemKind::Helmet | ItemKind::Chestplate | ItemKind::Leggings | ItemKind::Boots
+        );
+        let is_breakable = is_armor || matches!(kind, ItemKind::Sword | ItemKind::Digger);
+        match self {
+            Self::Armor | Self::Wearable => is_armor,
+            Self::ArmorFeet => matches!(kind, ItemKind::Boots),
+            Self::ArmorLegs => matches!(kind, ItemKind::Leggings),
+            Self::ArmorChest => matches!(kind, ItemKind::Chestplate),
+            Self::ArmorHead => matches!(kind, ItemKind::Helmet),
+            Self::Weapon => matches!(kind, ItemKind::Sword),
+            Self::Digger => matches!(kind, ItemKind::Digger),
+            Self::Breakable | Self::Vanishable => is_breakable,
+            Self::FishingRod | Self::Trident | Self::Bow | Self::Crossbow => false,
+        }
+    }
+}
+
+/// Enchantments in registry order, which is the order `EnchantmentHelper` walks them in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Enchantment {
+    Protection,
+    FireProtection,
+    FeatherFalling,
+    BlastProtection,
+    ProjectileProtection,
+    Respiration,
+    AquaAffinity,
+    Thorns,
+    DepthStrider,
+    FrostWalker,
+    BindingCurse,
+    SoulSpeed,
+    Sharpness,
+    Smite,
+    BaneOfArthropods,
+    Knockback,
+    FireAspect,
+    Looting,
+    Sweeping,
+    Efficiency,
+    SilkTouch,
+    Unbreaking,
+    Fortune,
+    Power,
+    Punch,
+    Flame,
+    Infinity,
+    LuckOfTheSea,
+    Lure,
+    Loyalty,
+    Impaling,
+    Riptide,
+    Channeling,
+    Multishot,
+    QuickCharge,
+    Piercing,
+    Mending,
+    VanishingCurse,
+}
+
+use Enchantment::*;
+
+impl Enchantment {
+    pub const ALL: [Self; 38] = [
+        Protection,
+        FireProtection,
+        FeatherFalling,
+        BlastProtection,
+        ProjectileProtection,
+        Respiration,
+        AquaAffinity,
+        Thorns,
+        DepthStrider,
+        FrostWalker,
+        BindingCurse,
+        SoulSpeed,
+        Sharpness,
+        Smite,
+        BaneOfArthropods,
+        Knockback,
+        FireAspect,
+        Looting,
+        Sweeping,
+        Efficiency,
+        SilkTouch,
+        Unbreaking,
+        Fortune,
+        Power,
+        Punch,
+        Flame,
+        Infinity,
+        LuckOfTheSea,
+        Lure,
+        Loyalty,
+        Impaling,
+        Riptide,
+        Channeling,
+        Multishot,
+        QuickCharge,
+        Piercing,
+        Mending,
+        VanishingCurse,
+    ];
+
+    pub const fn category(&self) -> EnchantmentCategory {
+        match self {
+            Protection | FireProtection | BlastProtection | ProjectileProtection => {
+                EnchantmentCategory::Armor
+            }
+            FeatherFalling | DepthStrider | FrostWalker | SoulSpeed => {
+                EnchantmentCategory::ArmorFeet
+            }
+            Respiration | AquaAffinity => EnchantmentCategory::ArmorHead,
+            Thorns => EnchantmentCategory::ArmorChest,
+            BindingCurse => EnchantmentCategory::Wearable,
+            Sharpness | Smite | BaneOfArthropods | Knockback | FireAspect | Looting | Sweeping => {
+                EnchantmentCategory::Weapon
+            }
+            Efficiency | SilkTouch | Fortune => EnchantmentCategory::Digger,
+            Unbreaking | Mending => EnchantmentCategory::Breakable,
+            Power | Punch | Flame | Infinity => EnchantmentCategory::Bow,
+            LuckOfTheSea | Lure => EnchantmentCategory::FishingRod,
+            Loyalty | Impaling | Riptide | Channeling => EnchantmentCategory::Trident,
+            Multishot | QuickCharge | Piercing => EnchantmentCategory::Crossbow,
+            VanishingCurse => EnchantmentCategory::Vanishable,
+        }
+    }
+
+    pub const fn weight(&self) -> i32 {
+        match self {
+            Protection | Sharpness | Efficiency | Power | Piercing => 10,
+            FireProtection | FeatherFalling | ProjectileProtection | Smite | BaneOfArthropods
+            | Knockback | Unbreaking | Loyalty | QuickCharge => 5,
+            Thorns | BindingCurse | SoulSpeed | SilkTouch | Infinity | Channeling
+            | VanishingCurse => 1,
+            _ => 2,
+        }
+    }
+
+    pub const fn max_level(&self) -> i32 {
+        match self {
+            Sharpness | Smite | BaneOfArthropods | Efficiency | Power | Impaling => 5,
+            Protection | FireProtection | FeatherFalling | BlastProtection
+            | ProjectileProtection | Piercing => 4,
+            Respiration | Thorns | DepthStrider | SoulSpeed | Looting | Sweeping | Unbreaking
+            | Fortune | LuckOfTheSea | Lure | Loyalty | Riptide | QuickCharge => 3,
+            FrostWalker | Knockback | FireAspect | Punch => 2,
+            _ => 1,
+        }
+    }
+
+    pub const fn min_cost(&self, level: i32) -> i32 {
+        match self {
+            Protection | Sharpness => 1 + (level - 1) * 11,
+            FireProtection => 10 + (level - 1) * 8,
+            FeatherFalling => 5 + (level - 1) * 6,
+            BlastProtection | Smite | BaneOfArthropods | Unbreaking => 5 + (level - 1) * 8,
+            ProjectileProtection => 3 + (level - 1) * 6,
+            Respiration | DepthStrider | FrostWalker | SoulSpeed => level * 10,
+            AquaAffinity => 1,
+            Thorns | FireAspect => 10 + 20 * (level - 1),
+            BindingCurse | VanishingCurse | Channeling => 25,
+            Knockback => 5 + 20 * (level - 1),
+            Looting | Fortune | LuckOfTheSea | Lure => 15 + (level - 1) * 9,
+            Sweeping => 5 + (level - 1) * 9,
+            Efficiency | Power | Piercing => 1 + (level - 1) * 10,
+            SilkTouch => 15,
+            Punch | QuickCharge => 12 + (level - 1) * 20,
+            Flame | Infinity | Multishot => 20,
+            Loyalty => 5 + level * 7,
+            Impaling => 1 + (level - 1) * 8,
+            Riptide => 10 + level * 7,
+            Mending => level * 25,
+        }
+    }
+
+    pub const fn max_cost(&self, level: i32) -> i32 {
+        let default_min_cost = 1 + level * 10;
+        match self {
+            Protection => self.min_cost(level) + 11,
+            FireProtection | BlastProtection => self.min_cost(level) + 8,
+            FeatherFalling | ProjectileProtection => self.min_cost(level) + 6,
+            Respiration => self.min_cost(level) + 30,
+            AquaAffinity => self.min_cost(level) + 40,
+            DepthStrider | FrostWalker | SoulSpeed | Power | Sweeping => self.min_cost(level) + 15,
+            Sharpness | Smite | BaneOfArthropods | Impaling => self.min_cost(level) + 20,
+            Punch => self.min_cost(level) + 25,
+            Mending => self.min_cost(level) + 50,
+            Thorns | Knockback | FireAspect | Looting | Efficiency | SilkTouch | Unbreaking
+            | Fortune | LuckOfTheSea | Lure => default_min_cost + 50,
+            _ => 50,
+        }
+    }
+
+    /// `Enchantment.canEnchant`, which thorns widens to every armor piece.
+    pub const fn can_enchant(&self, item: Item) -> bool {
+        match self {
+            Thorns => EnchantmentCategory::Armor.can_enchant(item),
+            _ => self.category().can_enchant(item),
+        }
+    }
+
+    pub const fn is_treasure_only(&self) -> bool {
+        matches!(
+            self,
+            FrostWalker | BindingCurse | SoulSpeed | Mending | VanishingCurse
+        )
+    }
+
+    pub const fn is_discoverable(&self) -> bool {
+        !matches!(self, SoulSpeed)
+    }
+
+    const fn is_protection(&self) -> bool {
+        matches!(
+            self,
+            Protection | FireProtection | FeatherFalling | BlastProtection | ProjectileProtection
+        )
+    }
+
+    const fn check_compatibility(&self, other: &Self) -> bool {
+        if *self as isize == *other as isize {
+            return false;
+        }
+        match self {
+            Protection | FireProtection | FeatherFalling | BlastProtection
+            | ProjectileProtection
+                if other.is_protection() =>
+            {
+                matches!(self, FeatherFalling) || matches!(other, FeatherFalling)
+            }
+            Sharpness | Smite | BaneOfArthropods => {
+                !matches!(other, Sharpness | Smite | BaneOfArthropods)
+            }
+            DepthStrider => !matches!(other, FrostWalker),
+            FrostWalker => !matches!(other, DepthStrider),
+            Infinity => !matches!(other, Mending),
+            SilkTouch => !matches!(other, Fortune),
+            Looting | Fortune | LuckOfTheSea => !matches!(other, SilkTouch),
+            Multishot => !matches!(other, Piercing),
+            Piercing => !matches!(other, Multishot),
+            Riptide => !matches!(other, Loyalty | Channeling),
+            _ => true,
+        }
+    }
+
+    pub const fn is_compatible_with(&self, other: &Self) -> bool {
+        self.check_compatibility(other) && other.check_compatibility(self)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EnchantmentInstance {
+    pub enchantment: Enchantment,
+    pub level: i32,
+}
+
+const fn enchanted(item: Item) -> Item {
+    match item {
+        Item::Book => Item::EnchantedBook,
+        _ => item,
+    }
+}
+
+/// `EnchantRandomlyFunction`: picks one enchantment from `options`, or from every
+/// discoverable enchantment that fits the item when `options` is empty.
+pub fn enchant_randomly(rand: &mut ChunkRand, item: Item, options: &[Enchantment]) -> Item {
+    let enchantment = if options.is_empty() {
+        let is_book = matches!(item.kind(), ItemKind::Book);
+        let mut available = Vec::with_capacity(Enchantment::ALL.len());
+        for enchantment in Enchantment::ALL {
+            if enchantment.is_discoverable() && (is_book || enchantment.can_enchant(item)) {
+                available.push(enchantment);
+            }
+        }
+        if available.is_empty() {
+            return item;
+        }
+        available[rand.get_next_int_bound(available.len() as i32) as usize]
+    } else {
+        options[rand.get_next_int_bound(options.len() as i32) as usize]
+    };
+    rand.get_int(1, enchantment.max_level());
+    enchanted(item)
+}
+
+fn get_available_enchantments(level: i32, item: Item, treasure: bool) -> Vec<EnchantmentInstance> {
+    let is_book = matches!(item.kind(), ItemKind::Book);
+    let mut available = Vec::new();
+    for enchantment in Enchantment::ALL {
+        if (enchantment.is_treasure_only() && !treasure)
+            || !enchantment.is_discoverable()
+            || !(is_book || enchantment.category().can_enchant(item))
+        {
+            continue;
+        }
+        for enchantment_level in (1..=enchantment.max_level()).rev() {
+            if level >= enchantment.min_cost(enchantment_level)
+                && level <= enchantment.max_cost(enchantment_level)
+            {
+                available.push(EnchantmentInstance {
+                    enchantment,
+                    level: enchantment_level,
+                });
+                break;
+            }
+        }
+    }
+    available
+}
+
+fn get_weighted(rand: &mut ChunkRand, list: &[EnchantmentInstance]) -> EnchantmentInstance {
+    let total: i32 = list.iter().map(|e| e.enchantment.weight()).sum();
+    let mut weight = rand.get_next_int_bound(total);
+    for instance in list {
+        weight -= instance.enchantment.weight();
+        if weight < 0 {
+            return *instance;
+        }
+    }
+    unreachable!("weight is below the total")
+}
+
+/// `EnchantmentHelper.selectEnchantment`, the part of `EnchantWithLevelsFunction` that
+/// consumes randomness.
+pub fn select_enchantments(
+    rand: &mut ChunkRand,
+    item: Item,
+    level: i32,
+    treasure: bool,
+) -> Vec<EnchantmentInstance> {
+    let mut selected = Vec::new();
+    let enchantment_value = item.enchantment_value();
+    if enchantment_value <= 0 {
+        return selected;
+    }
+    let mut level = level
+        + 1
+        + rand.get_next_int_bound(enchantment_value / 4 + 1)
+        + rand.get_next_int_bound(enchantment_value / 4 + 1);
+    let f = (rand.get_next_float() + rand.get_next_float() - 1.0) * 0.15;
+    // `Math.round` on Java's unfused float arithmetic.
+    #[allow(clippy::suboptimal_flops)]
+    let rounded = (level as f32 + level as f32 * f + 0.5).floor() as i32;
+    level = rounded.max(1);
+
+    let mut available = get_available_enchantments(level, item, treasure);
+    if available.is_empty() {
+        return selected;
+    }
+    selected.push(get_weighted(rand, &available));
+    while rand.get_next_int_bound(50) <= level {
+        let last = selected[selected.len() - 1].enchantment;
+        available.retain(|instance| last.is_compatible_with(&instance.enchantment));
+        if available.is_empty() {
+            break;
+        }
+        selected.push(get_weighted(rand, &available));
+        level /= 2;
+    }
+    selected
+}
+
+pub fn enchant_with_levels(rand: &mut ChunkRand, item: Item, level: i32, treasure: bool) -> Item {
+    select_enchantments(rand, item, level, treasure);
+    enchanted(item)
+}
@@ -0,0 +1,166 @@
+use crate::item::Item;
+use crate::loot::enchantment::{enchant_randomly, enchant_with_levels, Enchantment};
+use crate::random::chunkrand::ChunkRand;
+use crate::{shuffle_items, InventoryVec, ItemStack, DEFAULT_CONTAINER};
+
+pub mod enchantment;
+pub mod tables;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LootFunction {
+    SetCount(i32, i32),
+    SetDamage(f32, f32),
+    EnchantRandomly(&'static [Enchantment]),
+    EnchantWithLevels(i32, i32, bool),
+}
+
+impl LootFunction {
+    #[inline(always)]
+    fn apply(&self, rand: &mut ChunkRand, stack: ItemStack) -> ItemStack {
+        match *self {
+            Self::SetCount(min, max) => {
+                ItemStack::new(stack.get_item(), rand.get_int(min, max) as u8)
+            }
+            Self::SetDamage(min, max) => {
+                if min < max {
+                    rand.get_next_float();
+                }
+                stack
+            }
+            Self::EnchantRandomly(options) => ItemStack::new(
+                enchant_randomly(rand, stack.get_item(), options),
+                stack.get_count(),
+            ),
+            Self::EnchantWithLevels(min, max, treasure) => {
+                let level = rand.get_int(min, max);
+                ItemStack::new(
+                    enchant_with_levels(rand, stack.get_item(), level, treasure),
+                    stack.get_count(),
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LootEntry {
+    pub item: Item,
+    pub weight: i32,
+    pub functions: &'static [LootFunction],
+}
+
+impl LootEntry {
+    pub const fn new(item: Item, weight: i32, functions: &'static [LootFunction]) -> Self {
+        Self {
+            item,
+            weight,
+            functions,
+        }
+    }
+
+    #[inline(always)]
+    fn create_item_stack(&self, rand: &mut ChunkRand) -> ItemStack {
+        let mut stack = ItemStack::new(self.item, 1);
+        for function in self.functions {
+            stack = function.apply(rand, stack);
+        }
+        stack
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LootPool {
+    pub min_rolls: i32,
+    pub max_rolls: i32,
+    pub entries: &'static [LootEntry],
+    total_weight: i32,
+}
+
+impl LootPool {
+    pub const fn new(min_rolls: i32, max_rolls: i32, entries: &'static [LootEntry]) -> Self {
+        let mut total_weight = 0;
+        let mut i = 0;
+        while i < entries.len() {
+            total_weight += entries[i].weight;
+            i += 1;
+        }
+        Self {
+            min_rolls,
+            max_rolls,
+            entries,
+            total_weight,
+        }
+    }
+
+    #[inline(always)]
+    fn pick_entry(&self, rand: &mut ChunkRand) -> &LootEntry {
+        // A single entry is taken without consuming a weight roll.
+        if self.entries.len() == 1 {
+            return &self.entries[0];
+        }
+        let mut weight = rand.get_next_int_bound(self.total_weight);
+        for entry in self.entries {
+            weight -= entry.weight;
+            if weight < 0 {
+                return entry;
+            }
+        }
+        unreachable!("weight is below the total")
+    }
+
+    fn add_random_items(&self, rand: &mut ChunkRand, loot: &mut InventoryVec<ItemStack>) {
+        let rolls = rand.get_int(self.min_rolls, self.max_rolls);
+        for _ in 0..rolls {
+            let stack = self.pick_entry(rand).create_item_stack(rand);
+            if stack.is_empty() {
+                continue;
+            }
+            // `LootTable.createStackSplitter`: oversized stacks are split before filling.
+            let max_stack_size = stack.get_item().max_stack_size();
+            let mut count = stack.get_count();
+            while count > 0 {
+                let split = count.min(max_stack_size);
+                loot.push(ItemStack::new(stack.get_item(), split));
+                count -= split;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LootTable {
+    pub name: &'static str,
+    pub pools: &'static [LootPool],
+}
+
+impl LootTable {
+    pub const fn new(name: &'static str, pools: &'static [LootPool]) -> Self {
+        Self { name, pools }
+    }
+
+    /// The rolled item stacks in generation order, before they are spread over the container.
+    pub fn generate(&self, rand: &mut ChunkRand) -> InventoryVec<ItemStack> {
+        let mut loot = InventoryVec::new();
+        for pool in self.pools {
+            pool.add_random_items(rand, &mut loot);
+        }
+        loot
+    }
+
+    /// `LootTable.fill` for a 27 slot container.
+    pub fn fill(&self, rand: &mut ChunkRand) -> InventoryVec<ItemStack> {
+        let loot = self.generate(rand);
+        let mut container = DEFAULT_CONTAINER;
+        rand.shuffle(&mut container);
+        shuffle_items(rand, loot, container)
+    }
+
+    pub fn get_loot(&self, loot_seed: i64, indexed: bool) -> InventoryVec<ItemStack> {
+        let mut rand = ChunkRand::new(loot_seed);
+        if indexed {
+            self.fill(&mut rand)
+        } else {
+            self.generate(&mut rand)
+        }
+    }
+}
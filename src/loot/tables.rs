@@ -0,0 +1,487 @@
+use crate::item::Item;
+use crate::item::Item::*;
+use crate::loot::LootFunction::*;
+use crate::loot::{LootEntry, LootFunction, LootPool, LootTable};
+
+const fn item(item: Item, weight: i32) -> LootEntry {
+    LootEntry::new(item, weight, &[])
+}
+
+const fn counted(item: Item, weight: i32, functions: &'static [LootFunction]) -> LootEntry {
+    LootEntry::new(item, weight, functions)
+}
+
+pub const BURIED_TREASURE: LootTable = LootTable::new(
+    "chests/buried_treasure",
+    &[
+        LootPool::new(1, 1, &[item(HeartOfTheSea, 1)]),
+        LootPool::new(
+            5,
+            8,
+            &[
+                counted(IronIngot, 20, &[SetCount(1, 4)]),
+                counted(GoldIngot, 10, &[SetCount(1, 4)]),
+                counted(TNT, 5, &[SetCount(1, 2)]),
+            ],
+        ),
+        LootPool::new(
+            1,
+            3,
+            &[
+                counted(Emerald, 5, &[SetCount(4, 8)]),
+                counted(Diamond, 5, &[SetCount(1, 2)]),
+                counted(PrismarineCrystals, 5, &[SetCount(1, 5)]),
+            ],
+        ),
+        LootPool::new(0, 1, &[item(LeatherChestplate, 1), item(IronSword, 1)]),
+        LootPool::new(
+            2,
+            2,
+            &[
+                counted(CookedCod, 1, &[SetCount(2, 4)]),
+                counted(CookedSalmon, 1, &[SetCount(2, 4)]),
+            ],
+        ),
+    ],
+);
+
+const END_CITY_GEAR: &[LootFunction] = &[EnchantWithLevels(20, 39, true)];
+
+pub const END_CITY_TREASURE: LootTable = LootTable::new(
+    "chests/end_city_treasure",
+    &[LootPool::new(
+        2,
+        6,
+        &[
+            counted(Diamond, 5, &[SetCount(1, 3)]),
+            counted(IronIngot, 10, &[SetCount(4, 8)]),
+            counted(GoldIngot, 15, &[SetCount(2, 7)]),
+            counted(Emerald, 2, &[SetCount(2, 6)]),
+            counted(BeetrootSeeds, 5, &[SetCount(1, 10)]),
+            item(Saddle, 3),
+            item(IronHorseArmor, 1),
+            item(GoldenHorseArmor, 1),
+            item(DiamondHorseArmor, 1),
+            counted(DiamondSword, 3, END_CITY_GEAR),
+            counted(DiamondBoots, 3, END_CITY_GEAR),
+            counted(DiamondChestplate, 3, END_CITY_GEAR),
+            counted(DiamondLeggings, 3, END_CITY_GEAR),
+            counted(DiamondHelmet, 3, END_CITY_GEAR),
+            counted(DiamondPickaxe, 3, END_CITY_GEAR),
+            counted(DiamondShovel, 3, END_CITY_GEAR),
+            counted(IronSword, 3, END_CITY_GEAR),
+            counted(IronBoots, 3, END_CITY_GEAR),
+            counted(IronChestplate, 3, END_CITY_GEAR),
+            counted(IronLeggings, 3, END_CITY_GEAR),
+            counted(IronHelmet, 3, END_CITY_GEAR),
+            counted(IronPickaxe, 3, END_CITY_GEAR),
+            counted(IronShovel, 3, END_CITY_GEAR),
+        ],
+    )],
+);
+
+pub const WOODLAND_MANSION: LootTable = LootTable::new(
+    "chests/woodland_mansion",
+    &[
+        LootPool::new(
+            1,
+            3,
+            &[
+                item(Lead, 20),
+                item(GoldenApple, 15),
+                item(EnchantedGoldenApple, 2),
+                item(MusicDisc13, 15),
+                item(MusicDiscCat, 15),
+                item(NameTag, 20),
+                item(ChainmailChestplate, 10),
+                item(DiamondHoe, 15),
+                item(DiamondChestplate, 5),
+                counted(Book, 10, &[EnchantRandomly(&[])]),
+            ],
+        ),
+        LootPool::new(
+            1,
+            4,
+            &[
+                counted(IronIngot, 10, &[SetCount(1, 4)]),
+                counted(GoldIngot, 5, &[SetCount(1, 4)]),
+                item(Bread, 20),
+                counted(Wheat, 20, &[SetCount(1, 4)]),
+                item(Bucket, 10),
+                counted(Redstone, 15, &[SetCount(1, 4)]),
+                counted(Coal, 15, &[SetCount(1, 4)]),
+                counted(MelonSeeds, 10, &[SetCount(2, 4)]),
+                counted(PumpkinSeeds, 10, &[SetCount(2, 4)]),
+                counted(BeetrootSeeds, 10, &[SetCount(2, 4)]),
+            ],
+        ),
+        LootPool::new(
+            3,
+            3,
+            &[
+                counted(Bone, 10, &[SetCount(1, 8)]),
+                counted(Gunpowder, 10, &[SetCount(1, 8)]),
+                counted(RottenFlesh, 10, &[SetCount(1, 8)]),
+                counted(String, 10, &[SetCount(1, 8)]),
+            ],
+        ),
+    ],
+);
+
+const BASTION_GEAR: &[LootFunction] = &[SetDamage(0.8, 1.0), EnchantRandomly(&[])];
+
+/// The 1.16.2 treasure room table.
+pub const BASTION_TREASURE: LootTable = LootTable::new(
+    "chests/bastion_treasure",
+    &[
+        LootPool::new(
+            3,
+            3,
+            &[
+                item(NetheriteIngot, 15),
+                item(AncientDebris, 10),
+                item(NetheriteScrap, 8),
+                counted(AncientDebris, 4, &[SetCount(2, 2)]),
+                counted(DiamondSword, 6, BASTION_GEAR),
+                counted(DiamondChestplate, 6, BASTION_GEAR),
+                counted(DiamondHelmet, 6, BASTION_GEAR),
+                counted(DiamondLeggings, 6, BASTION_GEAR),
+                counted(DiamondBoots, 6, BASTION_GEAR),
+                item(DiamondSword, 6),
+                item(DiamondChestplate, 5),
+                item(DiamondHelmet, 5),
+                item(DiamondBoots, 5),
+                item(DiamondLeggings, 5),
+                counted(Diamond, 5, &[SetCount(2, 6)]),
+                item(EnchantedGoldenApple, 2),
+            ],
+        ),
+        LootPool::new(
+            3,
+            4,
+            &[
+                counted(SpectralArrow, 1, &[SetCount(12, 25)]),
+                counted(GoldBlock, 1, &[SetCount(2, 5)]),
+                counted(IronBlock, 1, &[SetCount(2, 5)]),
+                counted(GoldIngot, 1, &[SetCount(3, 9)]),
+                counted(IronIngot, 1, &[SetCount(3, 9)]),
+                counted(CryingObsidian, 1, &[SetCount(3, 5)]),
+                counted(Quartz, 1, &[SetCount(8, 23)]),
+                counted(GildedBlackstone, 1, &[SetCount(5, 15)]),
+                counted(MagmaCream, 1, &[SetCount(3, 8)]),
+            ],
+        ),
+    ],
+);
+
+pub const VILLAGE_WEAPONSMITH: LootTable = LootTable::new(
+    "chests/village/village_weaponsmith",
+    &[LootPool::new(
+        3,
+        8,
+        &[
+            counted(Diamond, 3, &[SetCount(1, 3)]),
+            counted(IronIngot, 10, &[SetCount(1, 5)]),
+            counted(GoldIngot, 5, &[SetCount(1, 3)]),
+            counted(Bread, 15, &[SetCount(1, 3)]),
+            counted(Apple, 15, &[SetCount(1, 3)]),
+            item(IronPickaxe, 5),
+            item(IronSword, 5),
+            item(IronChestplate, 5),
+            item(IronHelmet, 5),
+            item(IronLeggings, 5),
+            item(IronBoots, 5),
+            counted(Obsidian, 5, &[SetCount(3, 7)]),
+            counted(OakSapling, 5, &[SetCount(3, 7)]),
+            item(Saddle, 3),
+            item(IronHorseArmor, 1),
+            item(GoldenHorseArmor, 1),
+            item(DiamondHorseArmor, 1),
+        ],
+    )],
+);
+
+pub const VILLAGE_TOOLSMITH: LootTable = LootTable::new(
+    "chests/village/village_toolsmith",
+    &[LootPool::new(
+        3,
+        8,
+        &[
+            counted(Diamond, 1, &[SetCount(1, 3)]),
+            counted(IronIngot, 5, &[SetCount(1, 5)]),
+            counted(GoldIngot, 1, &[SetCount(1, 3)]),
+            counted(Bread, 15, &[SetCount(1, 3)]),
+            item(IronPickaxe, 5),
+            counted(Coal, 1, &[SetCount(1, 3)]),
+            counted(Stick, 20, &[SetCount(1, 3)]),
+            item(IronShovel, 5),
+        ],
+    )],
+);
+
+pub const VILLAGE_ARMORER: LootTable = LootTable::new(
+    "chests/village/village_armorer",
+    &[LootPool::new(
+        1,
+        5,
+        &[
+            counted(IronIngot, 2, &[SetCount(1, 3)]),
+            counted(Bread, 4, &[SetCount(1, 4)]),
+            item(IronHelmet, 1),
+            item(Emerald, 1),
+        ],
+    )],
+);
+
+pub const VILLAGE_CARTOGRAPHER: LootTable = LootTable::new(
+    "chests/village/village_cartographer",
+    &[LootPool::new(
+        1,
+        5,
+        &[
+            counted(Map, 10, &[SetCount(1, 3)]),
+            counted(Paper, 15, &[SetCount(1, 5)]),
+            item(Compass, 5),
+            counted(Bread, 15, &[SetCount(1, 4)]),
+            counted(Stick, 5, &[SetCount(1, 2)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_MASON: LootTable = LootTable::new(
+    "chests/village/village_mason",
+    &[LootPool::new(
+        1,
+        5,
+        &[
+            counted(ClayBall, 1, &[SetCount(1, 3)]),
+            item(FlowerPot, 1),
+            item(Stone, 2),
+            item(StoneBricks, 2),
+            counted(Bread, 4, &[SetCount(1, 4)]),
+            item(YellowDye, 1),
+            item(SmoothStone, 1),
+            item(Emerald, 1),
+        ],
+    )],
+);
+
+pub const VILLAGE_SHEPHERD: LootTable = LootTable::new(
+    "chests/village/village_shepherd",
+    &[LootPool::new(
+        1,
+        5,
+        &[
+            counted(WhiteWool, 6, &[SetCount(1, 8)]),
+            counted(BlackWool, 3, &[SetCount(1, 3)]),
+            counted(GrayWool, 2, &[SetCount(1, 3)]),
+            counted(BrownWool, 2, &[SetCount(1, 3)]),
+            counted(LightGrayWool, 2, &[SetCount(1, 3)]),
+            item(Emerald, 1),
+            item(Shears, 1),
+            counted(Wheat, 6, &[SetCount(1, 6)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_BUTCHER: LootTable = LootTable::new(
+    "chests/village/village_butcher",
+    &[LootPool::new(
+        1,
+        5,
+        &[
+            item(Emerald, 1),
+            counted(Porkchop, 6, &[SetCount(1, 3)]),
+            counted(Wheat, 6, &[SetCount(1, 3)]),
+            counted(Beef, 6, &[SetCount(1, 3)]),
+            counted(Mutton, 6, &[SetCount(1, 3)]),
+            counted(Coal, 3, &[SetCount(1, 3)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_FLETCHER: LootTable = LootTable::new(
+    "chests/village/village_fletcher",
+    &[LootPool::new(
+        1,
+        5,
+        &[
+            item(Emerald, 1),
+            counted(Arrow, 2, &[SetCount(1, 3)]),
+            counted(Feather, 6, &[SetCount(1, 3)]),
+            counted(Egg, 2, &[SetCount(1, 3)]),
+            counted(Flint, 6, &[SetCount(1, 3)]),
+            counted(Stick, 6, &[SetCount(1, 3)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_FISHER: LootTable = LootTable::new(
+    "chests/village/village_fisher",
+    &[LootPool::new(
+        1,
+        5,
+        &[
+            item(Emerald, 1),
+            counted(Cod, 2, &[SetCount(1, 3)]),
+            counted(Salmon, 1, &[SetCount(1, 3)]),
+            counted(WaterBucket, 1, &[SetCount(1, 3)]),
+            counted(Barrel, 1, &[SetCount(1, 3)]),
+            counted(WheatSeeds, 3, &[SetCount(1, 3)]),
+            counted(Coal, 2, &[SetCount(1, 3)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_TANNERY: LootTable = LootTable::new(
+    "chests/village/village_tannery",
+    &[LootPool::new(
+        1,
+        5,
+        &[
+            counted(Leather, 1, &[SetCount(1, 3)]),
+            item(LeatherChestplate, 2),
+            item(LeatherBoots, 2),
+            item(LeatherHelmet, 2),
+            counted(Bread, 5, &[SetCount(1, 4)]),
+            item(LeatherLeggings, 2),
+            item(Saddle, 1),
+            counted(Emerald, 1, &[SetCount(1, 4)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_TEMPLE: LootTable = LootTable::new(
+    "chests/village/village_temple",
+    &[LootPool::new(
+        3,
+        8,
+        &[
+            counted(Redstone, 2, &[SetCount(1, 4)]),
+            counted(Bread, 7, &[SetCount(1, 4)]),
+            counted(RottenFlesh, 7, &[SetCount(1, 4)]),
+            counted(LapisLazuli, 1, &[SetCount(1, 4)]),
+            counted(GoldIngot, 1, &[SetCount(1, 4)]),
+            counted(Emerald, 1, &[SetCount(1, 4)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_PLAINS_HOUSE: LootTable = LootTable::new(
+    "chests/village/village_plains_house",
+    &[LootPool::new(
+        3,
+        8,
+        &[
+            counted(GoldNugget, 1, &[SetCount(1, 3)]),
+            item(Dandelion, 2),
+            item(Poppy, 1),
+            counted(Potato, 10, &[SetCount(1, 5)]),
+            counted(Bread, 10, &[SetCount(1, 4)]),
+            counted(Apple, 10, &[SetCount(1, 5)]),
+            item(Book, 1),
+            item(Feather, 1),
+            counted(Emerald, 2, &[SetCount(1, 4)]),
+            counted(OakSapling, 5, &[SetCount(1, 2)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_SAVANNA_HOUSE: LootTable = LootTable::new(
+    "chests/village/village_savanna_house",
+    &[LootPool::new(
+        3,
+        8,
+        &[
+            counted(GoldNugget, 1, &[SetCount(1, 3)]),
+            item(Grass, 5),
+            item(TallGrass, 5),
+            counted(Bread, 10, &[SetCount(1, 4)]),
+            counted(WheatSeeds, 10, &[SetCount(1, 5)]),
+            counted(Emerald, 2, &[SetCount(1, 4)]),
+            counted(AcaciaSapling, 10, &[SetCount(1, 2)]),
+            item(Saddle, 1),
+            counted(Torch, 10, &[SetCount(1, 2)]),
+            item(Bucket, 1),
+        ],
+    )],
+);
+
+pub const VILLAGE_DESERT_HOUSE: LootTable = LootTable::new(
+    "chests/village/village_desert_house",
+    &[LootPool::new(
+        3,
+        8,
+        &[
+            item(ClayBall, 1),
+            item(GreenDye, 1),
+            counted(Cactus, 10, &[SetCount(1, 4)]),
+            counted(Wheat, 10, &[SetCount(1, 7)]),
+            counted(Bread, 10, &[SetCount(1, 4)]),
+            item(Book, 1),
+            counted(DeadBush, 2, &[SetCount(1, 3)]),
+            counted(Emerald, 1, &[SetCount(1, 3)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_SNOWY_HOUSE: LootTable = LootTable::new(
+    "chests/village/village_snowy_house",
+    &[LootPool::new(
+        3,
+        8,
+        &[
+            item(BlueIce, 1),
+            item(SnowBlock, 4),
+            counted(Potato, 10, &[SetCount(1, 7)]),
+            counted(Bread, 10, &[SetCount(1, 4)]),
+            counted(BeetrootSeeds, 10, &[SetCount(1, 5)]),
+            item(BeetrootSoup, 1),
+            item(Furnace, 1),
+            counted(Emerald, 1, &[SetCount(1, 4)]),
+            counted(Snowball, 10, &[SetCount(1, 7)]),
+            counted(Coal, 5, &[SetCount(1, 4)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_TAIGA_HOUSE: LootTable = LootTable::new(
+    "chests/village/village_taiga_house",
+    &[LootPool::new(
+        3,
+        8,
+        &[
+            counted(IronNugget, 1, &[SetCount(1, 5)]),
+            item(Fern, 2),
+            item(LargeFern, 2),
+            counted(Potato, 10, &[SetCount(1, 7)]),
+            counted(SweetBerries, 5, &[SetCount(1, 7)]),
+            counted(Bread, 10, &[SetCount(1, 4)]),
+            counted(PumpkinSeeds, 5, &[SetCount(1, 5)]),
+            item(PumpkinPie, 1),
+            counted(Emerald, 2, &[SetCount(1, 4)]),
+            counted(SpruceSapling, 5, &[SetCount(1, 5)]),
+            item(SpruceSign, 1),
+            counted(SpruceLog, 10, &[SetCount(1, 5)]),
+        ],
+    )],
+);
+
+pub const VILLAGE_CHESTS: [LootTable; 16] = [
+    VILLAGE_WEAPONSMITH,
+    VILLAGE_TOOLSMITH,
+    VILLAGE_ARMORER,
+    VILLAGE_CARTOGRAPHER,
+    VILLAGE_MASON,
+    VILLAGE_SHEPHERD,
+    VILLAGE_BUTCHER,
+    VILLAGE_FLETCHER,
+    VILLAGE_FISHER,
+    VILLAGE_TANNERY,
+    VILLAGE_TEMPLE,
+    VILLAGE_PLAINS_HOUSE,
+    VILLAGE_SAVANNA_HOUSE,
+    VILLAGE_DESERT_HOUSE,
+    VILLAGE_SNOWY_HOUSE,
+    VILLAGE_TAIGA_HOUSE,
+];
@@ -1,79 +1,472 @@
-use std::ffi::c_int;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::env;
+use std::fs;
+use std::io;
+use std::net::TcpListener;
+use std::ops::Range;
+use std::process;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
 use std::thread;
-use std::thread::JoinHandle;
-use std::time::Instant;
+use std::time::{Duration, Instant, SystemTime};
 
-use seedcracker::check_seed;
-use seedcracker::random::mth::get_mask;
+use seedcracker::anvil::RegionFile;
+use seedcracker::backend::cpu::CpuBackend;
+use seedcracker::backend::progress::ProgressTracker;
+use seedcracker::backend::{Backend, ScanReporter, ScanTarget, TreasureTarget};
+use seedcracker::distributed::coordinator::Coordinator;
+use seedcracker::distributed::worker::run_worker;
+use seedcracker::format::format_num;
+use seedcracker::hashed_seed::find_world_seeds;
+use seedcracker::joint::JointCracker;
+use seedcracker::observation::ChestObservation;
+use seedcracker::pos::{BlockPos, ChunkPos};
+use seedcracker::random::mcversion::{MCVersion, V1_13, V1_18};
+use seedcracker::report::{CrackReport, Observation, SeedResult};
+use seedcracker::structure::Structure;
+use seedcracker::text_seed::hash_world_seed_from_structure_seed;
+use seedcracker::{target_loot, DEFAULT_VERSION};
 
-const MAX_THREADS: u64 = 16;
+const CHUNK: ChunkPos = ChunkPos::new(-28, -73);
 
-const CHUNK_X: i32 = -28;
-const CHUNK_Z: i32 = -73;
+/// How many structure seeds there are, the default range of a scan.
+const STRUCTURE_SEEDS: u64 = 1 << 48;
 
-pub fn format_num(num: u64) -> String {
-    if num < 10_000 {
-        format!("{}", num)
-    } else if num < 1_000_000 {
-        format!("{:.0}k", (num as f64) / 1000.0)
-    } else if num < 10_000_000 {
-        format!("{:.1}M", (num as f64) / (1000.0 * 1000.0))
-    } else if num < 1_000_000_000 {
-        format!("{:.0}M", (num as f64) / (1000.0 * 1000.0))
-    } else if num < 10_000_000_000 {
-        format!("{:.1}B", (num as f64) / (1000.0 * 1000.0 * 1000.0))
-    } else if num < 1_000_000_000_000 {
-        format!("{:.0}B", (num as f64) / (1000.0 * 1000.0 * 1000.0))
-    } else if num < 10_000_000_000_000 {
-        format!("{:.1}T", (num as f64) / (1000.0 * 1000.0 * 1000.0 * 1000.0))
-    } else if num < 1_000_000_000_000_000 {
-        format!("{:.0}T", (num as f64) / (1000.0 * 1000.0 * 1000.0 * 1000.0))
-    } else {
-        format!(
-            "{:.2}Q",
-            (num as f64) / (1000.0 * 1000.0 * 1000.0 * 1000.0 * 1000.0)
-        )
+const DEFAULT_COORDINATOR: &str = "127.0.0.1:7878";
+
+/// Seeds in one leased work unit.
+const UNIT_SIZE: u64 = 1 << 30;
+
+/// A unit leased for longer than this is handed to the next worker that asks.
+const LEASE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// How often progress is printed while a scan runs.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The value following `name` on the command line.
+fn arg_value(name: &str) -> Option<String> {
+    let mut args = env::args().skip_while(|arg| arg != name);
+    args.next()?;
+    args.next()
+}
+
+/// Every value following `name`, for options that may be given more than once.
+fn arg_values(name: &str) -> Vec<String> {
+    let args: Vec<String> = env::args().collect();
+    args.windows(2)
+        .filter(|pair| pair[0] == name)
+        .map(|pair| pair[1].clone())
+        .collect()
+}
+
+/// `--range start..end`, every structure seed unless given.
+fn scan_range() -> Range<u64> {
+    let Some(range) = arg_value("--range") else {
+        return 0..STRUCTURE_SEEDS;
+    };
+    let parsed = range
+        .split_once("..")
+        .and_then(|(start, end)| Some(start.parse().ok()?..end.parse().ok()?));
+    match parsed {
+        Some(range) if range.start < range.end && range.end <= STRUCTURE_SEEDS => range,
+        _ => {
+            eprintln!("--range takes `start..end` within 0..{STRUCTURE_SEEDS}, got `{range}`");
+            process::exit(1);
+        }
     }
 }
 
-const MAX_SEEDS: u64 = get_mask(36) as u64;
+/// The chests given with `--observation <file>`, `--snbt <file>` and `--region <file.mca>`,
+/// exiting with the error if one can't be read. `--generated-in <version>` gives the
+/// release the region's chunks were generated in, which they can't always tell.
+fn read_observations() -> Vec<ChestObservation> {
+    let mut observations: Vec<ChestObservation> = arg_values("--observation")
+        .iter()
+        .map(|path| {
+            fs::read_to_string(path)
+                .map_err(|error| error.to_string())
+                .and_then(|text| ChestObservation::parse(&text))
+                .unwrap_or_else(|error| {
+                    eprintln!("{path}: {error}");
+                    process::exit(1);
+                })
+        })
+        .collect();
+    observations.extend(read_block_data());
+    for path in arg_values("--region") {
+        observations.extend(region_chests(&path, generated_in()));
+    }
+    observations
+}
 
-static mut SHOULD_STOP: bool = false;
+/// `--generated-in <version>`, exiting if it isn't a known release.
+fn generated_in() -> Option<MCVersion> {
+    let version = arg_value("--generated-in")?;
+    Some(MCVersion::parse(&version).unwrap_or_else(|| {
+        eprintln!("unknown version `{version}`");
+        process::exit(1);
+    }))
+}
 
-static FOUND_SEED: AtomicBool = AtomicBool::new(false);
-static SEEDS_SCANNED: AtomicU64 = AtomicU64::new(MAX_SEEDS);
+/// The chests of a region file, warning about the chunks that can't be read and exiting
+/// if the file itself can't be.
+fn region_chests(path: &str, generated_in: Option<MCVersion>) -> Vec<ChestObservation> {
+    let region = RegionFile::open(path).unwrap_or_else(|error| {
+        eprintln!("{path}: {error}");
+        process::exit(1);
+    });
+    let (chests, errors) = region.find_chests(generated_in);
+    for error in errors {
+        eprintln!("{path}: skipping {error}");
+    }
+    chests
+}
 
-fn main() {
-    let value = check_seed((-4872636734044769429_i64) as u64, CHUNK_X, CHUNK_Z);
-    let time = Instant::now();
-    let mut threads: Vec<JoinHandle<()>> = vec![];
-    for thread_idx in 0..MAX_THREADS {
-        threads.push(thread::spawn(move || {
-            if unsafe { SHOULD_STOP } {
-                return;
-            }
-            for lower48 in (thread_idx..MAX_SEEDS).step_by(MAX_THREADS as usize) {
-                if check_seed(lower48, CHUNK_X as c_int, CHUNK_Z as c_int) {
-                    if !FOUND_SEED.load(Ordering::SeqCst) {
-                        FOUND_SEED.store(true, Ordering::SeqCst);
-                        println!("Found seed `{lower48}`");
-                        SEEDS_SCANNED.store(lower48, Ordering::SeqCst);
-                        unsafe { SHOULD_STOP = true };
-                        break;
-                    } else {
-                        return;
+/// `seedcracker region <file.mca>... [--generated-in <version>]`: prints the chests found
+/// in region files as observations, to check or edit before a crack.
+fn region() {
+    let generated_in = generated_in();
+    let mut args = env::args().skip(2);
+    while let Some(path) = args.next() {
+        if path == "--generated-in" {
+            args.next();
+            continue;
+        }
+        for chest in region_chests(&path, generated_in) {
+            println!("# {path}\n{chest}");
+        }
+    }
+}
+
+/// The chests given with `--snbt <file>`, each holding the output of
+/// `/data get block x y z Items`; `-` reads it from stdin. A `--block x y z` before a
+/// file gives its coordinates when the output doesn't include them, and a
+/// `--structure <id>` the structure of the files after it, buried treasure by default.
+fn read_block_data() -> Vec<ChestObservation> {
+    let mut observations = Vec::new();
+    let mut block = None;
+    let mut structure = Structure::BuriedTreasure;
+    let mut args = env::args();
+    while let Some(arg) = args.next() {
+        let result = match arg.as_str() {
+            "--block" => {
+                let coordinates: Option<Vec<i32>> = args
+                    .by_ref()
+                    .take(3)
+                    .map(|coordinate| coordinate.parse().ok())
+                    .collect();
+                match coordinates.as_deref() {
+                    Some(&[x, y, z]) => block = Some(BlockPos::new(x, y, z)),
+                    _ => {
+                        eprintln!("--block needs three coordinates");
+                        process::exit(1);
                     }
                 }
+                continue;
+            }
+            "--structure" => {
+                let id = args.next().unwrap_or_default();
+                structure = Structure::from_id(&id).unwrap_or_else(|| {
+                    eprintln!("unknown structure `{id}`");
+                    process::exit(1);
+                });
+                continue;
+            }
+            "--snbt" => {
+                let path = args.next().unwrap_or_default();
+                let text = if path == "-" {
+                    io::read_to_string(io::stdin())
+                } else {
+                    fs::read_to_string(&path)
+                };
+                text.map_err(|error| error.to_string())
+                    .and_then(|text| {
+                        ChestObservation::from_block_data(&text, block.take(), structure)
+                    })
+                    .map_err(|error| format!("{path}: {error}"))
             }
-        }));
+            _ => continue,
+        };
+        match result {
+            Ok(observation) => observations.push(observation),
+            Err(error) => {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        }
+    }
+    observations
+}
+
+/// The built-in target without observations, a single buried treasure chest on its own,
+/// and a joint crack for anything else, which is also what a chest from a version
+/// `check_seed` does not seed like needs.
+fn scan_target(observations: &[ChestObservation]) -> Result<ScanTarget, String> {
+    match observations {
+        [] => Ok(ScanTarget::default()),
+        [observation]
+            if observation.structure == Structure::BuriedTreasure
+                && observation.version.is_newer_or_equal_to(&V1_13)
+                && observation.version.is_older_than(&V1_18) =>
+        {
+            Ok(ScanTarget::BuriedTreasure(TreasureTarget::new(
+                observation.chunk(),
+                observation.target,
+            )))
+        }
+        _ => JointCracker::new(observations.iter().map(ChestObservation::to_clue).collect())
+            .map(ScanTarget::Joint),
+    }
+}
+
+/// The structure seeds in `range` that the `LootTableSeed` of an unopened chest leads back
+/// to, so that only those are checked rather than the whole range. `None` when no
+/// observation has a loot seed that can be reversed, and opened chests are matched by
+/// their loot.
+fn loot_seed_candidates(observations: &[ChestObservation], range: &Range<u64>) -> Option<Vec<u64>> {
+    let seeds = observations.iter().find_map(|observation| {
+        observation
+            .chest()
+            .reverse_loot_seed(observation.loot_seed?)
+            .ok()
+    })?;
+    Some(
+        seeds
+            .into_iter()
+            .map(|seed| seed as u64)
+            .filter(|seed| range.contains(seed))
+            .collect(),
+    )
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ProgressFormat {
+    Text,
+    Json,
+    None,
+}
+
+impl ProgressFormat {
+    /// `--progress text|json|none`, text unless told otherwise.
+    fn from_args() -> Self {
+        match arg_value("--progress").as_deref() {
+            Some("json") => Self::Json,
+            Some("none") => Self::None,
+            _ => Self::Text,
+        }
     }
-    for thread in threads {
-        thread.join().unwrap();
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+    JsonLines,
+}
+
+impl OutputFormat {
+    /// `--output text|json|jsonl`, text unless told otherwise.
+    fn from_args() -> Self {
+        match arg_value("--output").as_deref() {
+            Some("json") => Self::Json,
+            Some("jsonl") => Self::JsonLines,
+            _ => Self::Text,
+        }
     }
-    let seeds_scanned = SEEDS_SCANNED.load(Ordering::SeqCst);
+}
+
+/// The world seeds worth reporting for a structure seed: the text seed it could come from,
+/// and those matching `--hashed-seed` when one is given.
+fn expand_world_seeds(structure_seed: u64, hashed_seed: Option<i64>) -> Vec<i64> {
+    let mut world_seeds: Vec<i64> = hash_world_seed_from_structure_seed(structure_seed)
+        .into_iter()
+        .collect();
+    if let Some(hashed_seed) = hashed_seed {
+        for world_seed in find_world_seeds(structure_seed, hashed_seed, &DEFAULT_VERSION) {
+            if !world_seeds.contains(&world_seed) {
+                world_seeds.push(world_seed);
+            }
+        }
+    }
+    world_seeds
+}
+
+/// Keeps the matching seeds, and stops the scan at the first one when `stop_at_first`.
+struct Hits {
+    seeds: Mutex<Vec<u64>>,
+    stop_at_first: bool,
+    progress: ProgressTracker,
+    output: OutputFormat,
+    hashed_seed: Option<i64>,
+}
+
+impl Hits {
+    fn result(&self, structure_seed: u64) -> SeedResult {
+        SeedResult {
+            structure_seed,
+            world_seeds: expand_world_seeds(structure_seed, self.hashed_seed),
+        }
+    }
+}
+
+impl ScanReporter for Hits {
+    fn hit(&self, seed: u64) {
+        let mut seeds = self.seeds.lock().unwrap();
+        // Threads already past the stop check may still report, only the first one counts.
+        if self.stop_at_first && !seeds.is_empty() {
+            return;
+        }
+        self.progress.add_hit();
+        match self.output {
+            OutputFormat::Text => println!("Found seed `{seed}`"),
+            OutputFormat::JsonLines => println!("{}", self.result(seed).to_json_line()),
+            OutputFormat::Json => {}
+        }
+        seeds.push(seed);
+    }
+
+    fn progress(&self, worker: usize, seeds: u64) {
+        self.progress.add_seeds(worker, seeds);
+    }
+
+    fn should_stop(&self) -> bool {
+        self.stop_at_first && !self.seeds.lock().unwrap().is_empty()
+    }
+}
+
+/// Prints the tracker's state every `PROGRESS_INTERVAL` until `done` is dropped.
+fn print_progress(progress: &ProgressTracker, format: ProgressFormat, done: Receiver<()>) {
+    while let Err(RecvTimeoutError::Timeout) = done.recv_timeout(PROGRESS_INTERVAL) {
+        let snapshot = progress.snapshot();
+        match format {
+            ProgressFormat::Text => eprintln!("{snapshot}"),
+            ProgressFormat::Json => eprintln!("{}", snapshot.to_json_line()),
+            ProgressFormat::None => {}
+        }
+    }
+}
+
+/// `seedcracker coordinate [addr] [--unit-size n] [--range start..end]`: serves the range to
+/// workers and prints the seeds they find once every unit is done.
+fn coordinate() {
+    let addr = env::args()
+        .nth(2)
+        .filter(|arg| !arg.starts_with("--"))
+        .unwrap_or_else(|| DEFAULT_COORDINATOR.to_string());
+    let unit_size = arg_value("--unit-size")
+        .and_then(|size| size.parse().ok())
+        .unwrap_or(UNIT_SIZE);
+    let Ok(ScanTarget::BuriedTreasure(target)) = scan_target(&read_observations()) else {
+        eprintln!("a coordinator scans for a single buried treasure chest");
+        process::exit(1);
+    };
+    let coordinator = Coordinator::new(target, scan_range(), unit_size, LEASE_TIMEOUT);
+    let listener = TcpListener::bind(&addr).expect("coordinator should bind");
+    eprintln!("Coordinating on {addr}");
+    let time = Instant::now();
+    coordinator
+        .serve(listener)
+        .expect("coordinator should serve");
+    for seed in coordinator.hits() {
+        println!("Found seed `{seed}`");
+    }
+    println!(
+        "{} seeds scanned in {:.3?}",
+        format_num(coordinator.scanned()),
+        time.elapsed()
+    );
+}
+
+/// `seedcracker work [addr]`: scans units leased from a coordinator on every core.
+fn work() {
+    let addr = env::args()
+        .nth(2)
+        .filter(|arg| !arg.starts_with("--"))
+        .unwrap_or_else(|| DEFAULT_COORDINATOR.to_string());
+    let units = run_worker(addr, &mut CpuBackend::default()).expect("worker should run");
+    eprintln!("Completed {units} units");
+}
+
+fn main() {
+    match env::args().nth(1).as_deref() {
+        Some("coordinate") => return coordinate(),
+        Some("work") => return work(),
+        Some("region") => return region(),
+        _ => {}
+    }
+    let format = ProgressFormat::from_args();
+    let output = OutputFormat::from_args();
+    let observations = read_observations();
+    let mut backend = CpuBackend::default();
+    let target = scan_target(&observations).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(1);
+    });
+    backend
+        .prepare(&target)
+        .expect("CPU backend should prepare");
+    let range = scan_range();
+    let candidates = loot_seed_candidates(&observations, &range);
+    let total = candidates
+        .as_ref()
+        .map_or(range.end - range.start, |seeds| seeds.len() as u64);
+    let reporter = Hits {
+        seeds: Mutex::new(Vec::new()),
+        stop_at_first: target.is_exact(),
+        progress: ProgressTracker::new(backend.workers(), total),
+        output,
+        hashed_seed: arg_value("--hashed-seed").and_then(|seed| seed.parse().ok()),
+    };
+    let started = SystemTime::now();
+    let time = Instant::now();
+    let seeds_scanned = thread::scope(|scope| {
+        let (done, finished) = mpsc::channel();
+        let progress = &reporter.progress;
+        scope.spawn(move || print_progress(progress, format, finished));
+        let seeds_scanned = match &candidates {
+            Some(seeds) => seeds
+                .iter()
+                .map(|&seed| {
+                    backend
+                        .scan(seed..seed + 1, &reporter)
+                        .expect("CPU backend should scan")
+                })
+                .sum(),
+            None => backend
+                .scan(range.clone(), &reporter)
+                .expect("CPU backend should scan"),
+        };
+        drop(done);
+        seeds_scanned
+    });
     let elapsed = time.elapsed();
-    if !FOUND_SEED.load(Ordering::SeqCst) {
+    let mut seeds = reporter.seeds.lock().unwrap().clone();
+    seeds.sort_unstable();
+    if output != OutputFormat::Text {
+        let report = CrackReport {
+            observations: if observations.is_empty() {
+                vec![Observation::from_inventory(
+                    Structure::BuriedTreasure,
+                    CHUNK,
+                    &target_loot(),
+                )]
+            } else {
+                observations.iter().map(Observation::from).collect()
+            },
+            backend: backend.name(),
+            range,
+            scanned: seeds_scanned,
+            started,
+            elapsed,
+            results: seeds.iter().map(|&seed| reporter.result(seed)).collect(),
+        };
+        if output == OutputFormat::Json {
+            println!("{}", report.to_json());
+        } else {
+            println!("{}", report.to_json_line());
+        }
+        return;
+    }
+    if seeds.is_empty() {
         println!("No seed found!");
     }
     println!("Stats:");
@@ -94,5 +487,8 @@ fn main() {
         "{:.3}ns per seed",
         (elapsed.as_nanos() as f64) / seeds_scanned as f64
     );
-    println!("{:.3} hours for checking all seeds", 2.0_f64.powi(48) /  (seeds_scanned as f64 / (elapsed.as_secs_f64() / 3600.0)))
+    println!(
+        "{:.3} hours for checking all seeds",
+        2.0_f64.powi(48) / (seeds_scanned as f64 / (elapsed.as_secs_f64() / 3600.0))
+    )
 }
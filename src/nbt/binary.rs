@@ -0,0 +1,188 @@
+use crate::nbt::Tag;
+
+const END: u8 = 0;
+const BYTE: u8 = 1;
+const SHORT: u8 = 2;
+const INT: u8 = 3;
+const LONG: u8 = 4;
+const FLOAT: u8 = 5;
+const DOUBLE: u8 = 6;
+const BYTE_ARRAY: u8 = 7;
+const STRING: u8 = 8;
+const LIST: u8 = 9;
+const COMPOUND: u8 = 10;
+const INT_ARRAY: u8 = 11;
+const LONG_ARRAY: u8 = 12;
+
+/// Reads an uncompressed NBT document, returning the name and value of its root tag.
+pub fn read(bytes: &[u8]) -> Result<(String, Tag), String> {
+    let mut reader = Reader { bytes, pos: 0 };
+    let kind = reader.u8()?;
+    if kind != COMPOUND {
+        return Err(format!(
+            "the root tag should be a compound, not type {kind}"
+        ));
+    }
+    let name = reader.string()?;
+    Ok((name, reader.payload(COMPOUND, 0)?))
+}
+
+/// Writes `tag` as an uncompressed NBT document under the root name `name`.
+pub fn write(name: &str, tag: &Tag) -> Vec<u8> {
+    let mut out = vec![kind(tag)];
+    write_string(&mut out, name);
+    write_payload(&mut out, tag);
+    out
+}
+
+/// How deep compounds and lists may nest, like the game's own limit.
+pub(crate) const MAX_DEPTH: usize = 512;
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + N)
+            .ok_or("unexpected end of NBT data")?;
+        self.pos += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn len(&mut self) -> Result<usize, String> {
+        let len = i32::from_be_bytes(self.take()?);
+        usize::try_from(len).map_err(|_| format!("negative length {len}"))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = u16::from_be_bytes(self.take()?) as usize;
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + len)
+            .ok_or("unexpected end of NBT data")?;
+        self.pos += len;
+        // Java's modified UTF-8 only differs for NUL and supplementary characters.
+        Ok(String::from_utf8_lossy(bytes).into_owned())
+    }
+
+    fn array<T, const N: usize>(&mut self, from: fn([u8; N]) -> T) -> Result<Vec<T>, String> {
+        let len = self.len()?;
+        if len * N > self.bytes.len() - self.pos {
+            return Err("unexpected end of NBT data".to_string());
+        }
+        (0..len).map(|_| Ok(from(self.take()?))).collect()
+    }
+
+    fn payload(&mut self, kind: u8, depth: usize) -> Result<Tag, String> {
+        if depth > MAX_DEPTH {
+            return Err("NBT nested too deeply".to_string());
+        }
+        Ok(match kind {
+            BYTE => Tag::Byte(i8::from_be_bytes(self.take()?)),
+            SHORT => Tag::Short(i16::from_be_bytes(self.take()?)),
+            INT => Tag::Int(i32::from_be_bytes(self.take()?)),
+            LONG => Tag::Long(i64::from_be_bytes(self.take()?)),
+            FLOAT => Tag::Float(f32::from_be_bytes(self.take()?)),
+            DOUBLE => Tag::Double(f64::from_be_bytes(self.take()?)),
+            BYTE_ARRAY => Tag::ByteArray(self.array(i8::from_be_bytes)?),
+            STRING => Tag::String(self.string()?),
+            LIST => {
+                let kind = self.u8()?;
+                let len = self.len()?;
+                let mut values = Vec::with_capacity(len.min(1024));
+                for _ in 0..len {
+                    values.push(self.payload(kind, depth + 1)?);
+                }
+                Tag::List(values)
+            }
+            COMPOUND => {
+                let mut entries = Vec::new();
+                loop {
+                    let kind = self.u8()?;
+                    if kind == END {
+                        break;
+                    }
+                    let name = self.string()?;
+                    entries.push((name, self.payload(kind, depth + 1)?));
+                }
+                Tag::Compound(entries)
+            }
+            INT_ARRAY => Tag::IntArray(self.array(i32::from_be_bytes)?),
+            LONG_ARRAY => Tag::LongArray(self.array(i64::from_be_bytes)?),
+            _ => return Err(format!("unknown tag type {kind}")),
+        })
+    }
+}
+
+const fn kind(tag: &Tag) -> u8 {
+    match tag {
+        Tag::Byte(_) => BYTE,
+        Tag::Short(_) => SHORT,
+        Tag::Int(_) => INT,
+        Tag::Long(_) => LONG,
+        Tag::Float(_) => FLOAT,
+        Tag::Double(_) => DOUBLE,
+        Tag::ByteArray(_) => BYTE_ARRAY,
+        Tag::String(_) => STRING,
+        Tag::List(_) => LIST,
+        Tag::Compound(_) => COMPOUND,
+        Tag::IntArray(_) => INT_ARRAY,
+        Tag::LongArray(_) => LONG_ARRAY,
+    }
+}
+
+fn write_string(out: &mut Vec<u8>, value: &str) {
+    out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    out.extend_from_slice(value.as_bytes());
+}
+
+fn write_payload(out: &mut Vec<u8>, tag: &Tag) {
+    match tag {
+        Tag::Byte(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Short(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Int(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Long(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Float(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::Double(value) => out.extend_from_slice(&value.to_be_bytes()),
+        Tag::ByteArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            values
+                .iter()
+                .for_each(|value| out.extend_from_slice(&value.to_be_bytes()));
+        }
+        Tag::String(value) => write_string(out, value),
+        Tag::List(values) => {
+            out.push(values.first().map_or(END, kind));
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            values.iter().for_each(|value| write_payload(out, value));
+        }
+        Tag::Compound(entries) => {
+            for (name, value) in entries {
+                out.push(kind(value));
+                write_string(out, name);
+                write_payload(out, value);
+            }
+            out.push(END);
+        }
+        Tag::IntArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            values
+                .iter()
+                .for_each(|value| out.extend_from_slice(&value.to_be_bytes()));
+        }
+        Tag::LongArray(values) => {
+            out.extend_from_slice(&(values.len() as i32).to_be_bytes());
+            values
+                .iter()
+                .for_each(|value| out.extend_from_slice(&value.to_be_bytes()));
+        }
+    }
+}
@@ -0,0 +1,58 @@
+pub mod binary;
+pub mod snbt;
+
+/// One NBT value, as stored in region files or printed by `/data get`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Self>),
+    /// Entries keep the order they were read in.
+    Compound(Vec<(String, Self)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Tag {
+    /// The entry `key` of a compound.
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Compound(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, tag)| tag),
+            _ => None,
+        }
+    }
+
+    /// Any integer tag, widened.
+    pub const fn as_i64(&self) -> Option<i64> {
+        match self {
+            Self::Byte(value) => Some(*value as i64),
+            Self::Short(value) => Some(*value as i64),
+            Self::Int(value) => Some(*value as i64),
+            Self::Long(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Self]> {
+        match self {
+            Self::List(values) => Some(values),
+            _ => None,
+        }
+    }
+}
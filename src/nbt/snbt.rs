@@ -0,0 +1,199 @@
+use crate::nbt::binary::MAX_DEPTH;
+use crate::nbt::Tag;
+
+/// Reads stringified NBT, the text form `/data get` prints and commands accept.
+pub fn parse(text: &str) -> Result<Tag, String> {
+    let mut parser = Parser {
+        text: text.as_bytes(),
+        pos: 0,
+    };
+    let tag = parser.value(0)?;
+    parser.skip_whitespace();
+    if parser.pos != parser.text.len() {
+        return Err(parser.error("trailing characters"));
+    }
+    Ok(tag)
+}
+
+const fn is_unquoted(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, b'_' | b'-' | b'.' | b'+')
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        format!("{message} at column {}", self.pos + 1)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.text.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.text.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), String> {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", c as char)))
+        }
+    }
+
+    /// A value `depth` compounds and lists deep, which is limited like binary NBT so a
+    /// pasted `[[[[…` can't overflow the stack.
+    fn value(&mut self, depth: usize) -> Result<Tag, String> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("NBT nested too deeply"));
+        }
+        match self.peek() {
+            Some(b'{') => self.compound(depth),
+            Some(b'[') => self.list(depth),
+            Some(b'"' | b'\'') => self.quoted().map(Tag::String),
+            Some(_) => self.literal(),
+            None => Err(self.error("expected a value")),
+        }
+    }
+
+    fn compound(&mut self, depth: usize) -> Result<Tag, String> {
+        self.expect(b'{')?;
+        let mut entries = Vec::new();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(Tag::Compound(entries));
+        }
+        loop {
+            let key = match self.peek() {
+                Some(b'"' | b'\'') => self.quoted()?,
+                _ => self.unquoted()?,
+            };
+            self.expect(b':')?;
+            entries.push((key, self.value(depth + 1)?));
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(Tag::Compound(entries));
+                }
+                _ => return Err(self.error("expected `,` or `}`")),
+            }
+        }
+    }
+
+    /// A list, or a typed array like `[I; 1, 2]`.
+    fn list(&mut self, depth: usize) -> Result<Tag, String> {
+        self.expect(b'[')?;
+        let array = match (self.peek(), self.text.get(self.pos + 1)) {
+            (Some(kind @ (b'B' | b'I' | b'L')), Some(b';')) => {
+                self.pos += 2;
+                Some(kind)
+            }
+            _ => None,
+        };
+        let mut values = Vec::new();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+        } else {
+            loop {
+                values.push(self.value(depth + 1)?);
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(b']') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(self.error("expected `,` or `]`")),
+                }
+            }
+        }
+        let integers = || {
+            values
+                .iter()
+                .map(Tag::as_i64)
+                .collect::<Option<Vec<i64>>>()
+                .ok_or_else(|| self.error("typed arrays only hold integers"))
+        };
+        Ok(match array {
+            Some(b'B') => Tag::ByteArray(integers()?.into_iter().map(|v| v as i8).collect()),
+            Some(b'I') => Tag::IntArray(integers()?.into_iter().map(|v| v as i32).collect()),
+            Some(_) => Tag::LongArray(integers()?),
+            None => Tag::List(values),
+        })
+    }
+
+    fn quoted(&mut self) -> Result<String, String> {
+        let quote = self.text[self.pos];
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            match self.text.get(self.pos) {
+                Some(&c) if c == quote => {
+                    self.pos += 1;
+                    return String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8"));
+                }
+                Some(b'\\') => {
+                    let escaped = *self
+                        .text
+                        .get(self.pos + 1)
+                        .ok_or_else(|| self.error("unterminated string"))?;
+                    bytes.push(escaped);
+                    self.pos += 2;
+                }
+                Some(&c) => {
+                    bytes.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn unquoted(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self.text.get(self.pos).copied().is_some_and(is_unquoted) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Err(self.error("expected a name"));
+        }
+        Ok(String::from_utf8_lossy(&self.text[start..self.pos]).into_owned())
+    }
+
+    /// A number with an optional type suffix, a boolean, or else an unquoted string.
+    fn literal(&mut self) -> Result<Tag, String> {
+        let word = self.unquoted()?;
+        let (digits, suffix) = match word.as_bytes()[word.len() - 1] {
+            c @ (b'b' | b'B' | b's' | b'S' | b'l' | b'L' | b'f' | b'F' | b'd' | b'D') => {
+                (&word[..word.len() - 1], Some(c.to_ascii_lowercase()))
+            }
+            _ => (word.as_str(), None),
+        };
+        let tag = match suffix {
+            Some(b'b') => digits.parse().ok().map(Tag::Byte),
+            Some(b's') => digits.parse().ok().map(Tag::Short),
+            Some(b'l') => digits.parse().ok().map(Tag::Long),
+            Some(b'f') => digits.parse().ok().map(Tag::Float),
+            Some(_) => digits.parse().ok().map(Tag::Double),
+            None => word.parse().ok().map(Tag::Int).or_else(|| {
+                word.contains(['.', 'e', 'E'])
+                    .then(|| word.parse().ok().map(Tag::Double))
+                    .flatten()
+            }),
+        };
+        Ok(tag.unwrap_or(match word.as_str() {
+            "true" => Tag::Byte(1),
+            "false" => Tag::Byte(0),
+            _ => Tag::String(word),
+        }))
+    }
+}
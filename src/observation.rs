@@ -0,0 +1,507 @@
+use std::fmt;
+
+use crate::item::Item;
+use crate::joint::Clue;
+use crate::loot::LootTable;
+use crate::nbt::{snbt, Tag};
+use crate::pos::{BlockPos, ChunkPos};
+use crate::random::mcversion::MCVersion;
+use crate::structure::{Structure, StructureChest};
+use crate::village::VillageCracker;
+use crate::{InventoryVec, ItemStack, DEFAULT_VERSION};
+
+pub const SLOTS: usize = 27;
+
+/// What `/data get block` prints between the coordinates and the data.
+const BLOCK_DATA: &str = "has the following block data:";
+
+/// The contents a chest is compared against, `None` where a slot was not seen.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LootTarget {
+    pub slots: [Option<ItemStack>; SLOTS],
+}
+
+impl LootTarget {
+    pub const fn unknown() -> Self {
+        Self {
+            slots: [None; SLOTS],
+        }
+    }
+
+    /// Every slot known, as in `target_loot`.
+    pub fn exact(inventory: &InventoryVec<ItemStack>) -> Self {
+        let mut target = Self::unknown();
+        for (slot, stack) in inventory.iter().enumerate().take(SLOTS) {
+            target.slots[slot] = Some(*stack);
+        }
+        target
+    }
+
+    /// A container's `Items` list. Slots it leaves out are empty, so every slot is known.
+    pub fn from_items(items: &Tag) -> Result<Self, String> {
+        let mut target = Self {
+            slots: [Some(ItemStack::new(Item::Empty, 0)); SLOTS],
+        };
+        let items = items.as_list().ok_or("`Items` should be a list")?;
+        for item in items {
+            let slot = item
+                .get("Slot")
+                .and_then(Tag::as_i64)
+                .ok_or("an item has no `Slot`")?;
+            let id = item
+                .get("id")
+                .and_then(Tag::as_str)
+                .ok_or_else(|| format!("the item in slot {slot} has no `id`"))?;
+            // `count` replaced `Count` in 1.20.5.
+            let count = item
+                .get("Count")
+                .or_else(|| item.get("count"))
+                .and_then(Tag::as_i64)
+                .unwrap_or(1);
+            let slot = usize::try_from(slot)
+                .ok()
+                .filter(|&slot| slot < SLOTS)
+                .ok_or_else(|| format!("slot {slot} is not in 0..{SLOTS}"))?;
+            target.slots[slot] = parse_slot(&format!("{id} x{count}"))
+                .map_err(|error| format!("slot {slot}: {error}"))?;
+        }
+        Ok(target)
+    }
+
+    pub fn known_slots(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.known_slots() == SLOTS
+    }
+
+    /// How many of `item` the known slots hold, and whether every slot is known so that
+    /// this is the chest's whole count. With unknown slots the chest holds at least as many.
+    pub fn count(&self, item: Item) -> (u32, bool) {
+        let count = self
+            .slots
+            .iter()
+            .flatten()
+            .filter(|stack| stack.get_item() == item)
+            .map(|stack| u32::from(stack.get_count()))
+            .sum();
+        (count, self.known_slots() == SLOTS)
+    }
+
+    /// Whether generated loot agrees with every known slot. An empty slot matches any empty
+    /// stack, however the generator spelled it.
+    pub fn matches(&self, loot: &InventoryVec<ItemStack>) -> bool {
+        self.slots.iter().enumerate().all(|(slot, expected)| {
+            let Some(expected) = expected else {
+                return true;
+            };
+            match loot.get(slot) {
+                Some(stack) if stack.is_empty() || expected.is_empty() => {
+                    stack.is_empty() && expected.is_empty()
+                }
+                Some(stack) => stack == expected,
+                None => expected.is_empty(),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Container {
+    Chest,
+    TrappedChest,
+    Barrel,
+}
+
+impl Container {
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Self::Chest => "minecraft:chest",
+            Self::TrappedChest => "minecraft:trapped_chest",
+            Self::Barrel => "minecraft:barrel",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id.strip_prefix("minecraft:").unwrap_or(id) {
+            "chest" => Some(Self::Chest),
+            "trapped_chest" => Some(Self::TrappedChest),
+            "barrel" => Some(Self::Barrel),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Position {
+    Chunk(ChunkPos),
+    Block(BlockPos),
+}
+
+impl Position {
+    pub const fn chunk(&self) -> ChunkPos {
+        match self {
+            Self::Chunk(chunk) => *chunk,
+            Self::Block(block) => block.to_chunk(),
+        }
+    }
+}
+
+/// One chest as it was seen in the world.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChestObservation {
+    pub structure: Structure,
+    pub position: Position,
+    pub container: Container,
+    pub loot_table: &'static LootTable,
+    /// Which of the chunk's containers this is, in generation order.
+    pub chest_index: u32,
+    /// The chunk a village started in, which rules out most seeds before any loot is rolled.
+    pub start_chunk: Option<ChunkPos>,
+    /// The release the chest was generated in, which decides how its loot is seeded.
+    pub version: MCVersion,
+    /// The `LootTableSeed` of an unopened chest, the `nextLong` its chunk random gave it.
+    /// It leads back to the structure seed without rolling any loot, see
+    /// `StructureChest::reverse_loot_seed`.
+    pub loot_seed: Option<i64>,
+    pub target: LootTarget,
+}
+
+fn parse_coordinates<const N: usize>(value: &str) -> Result<[i32; N], String> {
+    let coordinates: Vec<i32> = value
+        .split_whitespace()
+        .map(|coordinate| {
+            coordinate
+                .parse()
+                .map_err(|_| format!("`{coordinate}` is not a coordinate"))
+        })
+        .collect::<Result<_, _>>()?;
+    coordinates
+        .try_into()
+        .map_err(|_| format!("expected {N} coordinates, got `{value}`"))
+}
+
+/// The table `name`, if `structure` places chests with it.
+pub fn find_loot_table(structure: Structure, name: &str) -> Option<&'static LootTable> {
+    let name = name.strip_prefix("minecraft:").unwrap_or(name);
+    structure
+        .loot_tables()
+        .iter()
+        .find(|table| table.name == name)
+}
+
+/// Rejects a container the structure never puts its loot in.
+fn check_container(structure: Structure, container: Container) -> Result<(), String> {
+    if structure == Structure::BuriedTreasure && container != Container::Chest {
+        return Err(format!(
+            "a buried treasure is always a chest, not a {}",
+            container.id()
+        ));
+    }
+    Ok(())
+}
+
+/// `minecraft:diamond x2`, `diamond`, `empty` or `?`.
+pub fn parse_slot(value: &str) -> Result<Option<ItemStack>, String> {
+    let mut fields = value.split_whitespace();
+    let id = fields.next().ok_or("missing item")?;
+    if id == "?" {
+        return Ok(None);
+    }
+    if id == "empty" {
+        return Ok(Some(ItemStack::new(Item::Empty, 0)));
+    }
+    let item = Item::from_id(id).ok_or_else(|| format!("unknown item `{id}`"))?;
+    if item == Item::Empty {
+        return Ok(Some(ItemStack::new(Item::Empty, 0)));
+    }
+    let count = match fields.next() {
+        Some(count) => count
+            .strip_prefix('x')
+            .and_then(|count| count.parse::<u32>().ok())
+            .ok_or_else(|| format!("`{count}` is not a count like `x3`"))?,
+        None => 1,
+    };
+    if let Some(extra) = fields.next() {
+        return Err(format!("unexpected `{extra}` after the count"));
+    }
+    if count == 0 {
+        return Err(format!("a stack of {id} can't be empty"));
+    }
+    if count > u32::from(item.max_stack_size()) {
+        return Err(format!(
+            "{count} {id} is more than a stack of {}",
+            item.max_stack_size()
+        ));
+    }
+    Ok(Some(ItemStack::new(item, count as u8)))
+}
+
+impl ChestObservation {
+    /// Reads what was seen in a chest:
+    ///
+    /// ```text
+    /// # The buried treasure near spawn
+    /// structure: buried_treasure
+    /// chunk: -28 -73
+    /// container: chest
+    /// 0: minecraft:leather_chestplate x1
+    /// 6: empty
+    /// 13: minecraft:diamond x1
+    /// 14: ?
+    /// ```
+    ///
+    /// `block: x y z` may be given instead of `chunk`. `structure` defaults to buried
+    /// treasure and `container` to a chest; other structures may pick a `loot_table` and
+    /// the `index` of the chest among the draws of its chunk random (see `get_loot_seed`),
+    /// and a village chest may give the `start: x z` chunk of its village. `version` is the
+    /// release the chest was generated in, 1.16.5 unless given, and `loot_seed` the
+    /// `LootTableSeed` of a chest that was never opened. Slots that are not
+    /// listed, or listed as `?`, are unknown and match anything. The count may be left out
+    /// for a single item.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut structure = Structure::BuriedTreasure;
+        let mut position = None;
+        let mut container = Container::Chest;
+        let mut loot_table = None;
+        let mut chest_index = 0;
+        let mut start_chunk = None;
+        let mut version = DEFAULT_VERSION;
+        let mut loot_seed = None;
+        let mut target = LootTarget::unknown();
+        let mut seen = [false; SLOTS];
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: String| format!("line {}: {message}", number + 1);
+            let (key, value) = line
+                .split_once(':')
+                .filter(|(key, _)| !key.contains(char::is_whitespace))
+                .ok_or_else(|| error(format!("expected `key: value`, got `{line}`")))?;
+            let value = value.trim();
+
+            if let Ok(slot) = key.parse::<usize>() {
+                if slot >= SLOTS {
+                    return Err(error(format!("slot {slot} is not in 0..{SLOTS}")));
+                }
+                if seen[slot] {
+                    return Err(error(format!("slot {slot} is listed twice")));
+                }
+                seen[slot] = true;
+                target.slots[slot] = parse_slot(value).map_err(error)?;
+                continue;
+            }
+            match key {
+                "structure" => {
+                    structure = Structure::from_id(value)
+                        .ok_or_else(|| error(format!("unknown structure `{value}`")))?;
+                }
+                "chunk" => {
+                    let [x, z] = parse_coordinates(value).map_err(error)?;
+                    position = Some(Position::Chunk(ChunkPos::new(x, z)));
+                }
+                "block" => {
+                    let [x, y, z] = parse_coordinates(value).map_err(error)?;
+                    position = Some(Position::Block(BlockPos::new(x, y, z)));
+                }
+                "container" => {
+                    container = Container::from_id(value)
+                        .ok_or_else(|| error(format!("unknown container `{value}`")))?;
+                }
+                "loot_table" => loot_table = Some((number, value)),
+                "index" => {
+                    chest_index = value
+                        .parse()
+                        .map_err(|_| error(format!("`{value}` is not a chest index")))?;
+                }
+                "start" => {
+                    let [x, z] = parse_coordinates(value).map_err(error)?;
+                    start_chunk = Some(ChunkPos::new(x, z));
+                }
+                "version" => {
+                    version = MCVersion::parse(value)
+                        .ok_or_else(|| error(format!("unknown version `{value}`")))?;
+                }
+                "loot_seed" => {
+                    loot_seed = Some(
+                        value
+                            .parse()
+                            .map_err(|_| error(format!("`{value}` is not a loot seed")))?,
+                    );
+                }
+                _ => return Err(error(format!("unknown key `{key}`"))),
+            }
+        }
+
+        let loot_table = match loot_table {
+            Some((number, name)) => find_loot_table(structure, name).ok_or_else(|| {
+                format!(
+                    "line {}: {} has no loot table `{name}`",
+                    number + 1,
+                    structure.id()
+                )
+            })?,
+            None => &structure.loot_tables()[0],
+        };
+        check_container(structure, container)?;
+        if start_chunk.is_some() && structure != Structure::Village {
+            return Err(format!(
+                "only a village has a `start`, not {}",
+                structure.id()
+            ));
+        }
+        Ok(Self {
+            structure,
+            position: position.ok_or("missing `chunk` or `block`")?,
+            container,
+            loot_table,
+            chest_index,
+            start_chunk,
+            version,
+            loot_seed,
+            target,
+        })
+    }
+
+    /// A chest of `structure` from what `/data get block x y z` or `/data get block x y z
+    /// Items` printed, with or without the rest of the chat log line. The coordinates are
+    /// read from the `x, y, z has the following block data:` prefix unless `block` is given.
+    ///
+    /// The whole block data of an unopened chest names its `LootTable`, which must be one
+    /// of the structure's, and has no `Items` but the `LootTableSeed` they will be rolled
+    /// from. That seed is kept as the observation's `loot_seed`, and the loot it rolls as
+    /// an exact target. Otherwise the structure's first table is assumed, as in `parse`.
+    pub fn from_block_data(
+        text: &str,
+        block: Option<BlockPos>,
+        structure: Structure,
+    ) -> Result<Self, String> {
+        let (prefix, data) = match text.split_once(BLOCK_DATA) {
+            Some((prefix, data)) => (Some(prefix), data),
+            None => (None, text),
+        };
+        let block = match (block, prefix) {
+            (Some(block), _) => block,
+            (None, Some(prefix)) => {
+                // The coordinates are the last three values before the marker, whatever
+                // the log put in front of them.
+                let mut fields = prefix.rsplitn(3, ',');
+                let (z, y) = (fields.next(), fields.next());
+                let x = fields.next().and_then(|x| x.split_whitespace().last());
+                let (Some(x), Some(y), Some(z)) = (x, y, z) else {
+                    return Err(format!("no block coordinates in `{}`", prefix.trim()));
+                };
+                let [x, y, z] = parse_coordinates(&format!("{x} {y} {z}"))?;
+                BlockPos::new(x, y, z)
+            }
+            (None, None) => return Err("no block coordinates given".to_string()),
+        };
+        let data = snbt::parse(data.trim())?;
+        let loot_table = match data.get("LootTable").and_then(Tag::as_str) {
+            Some(name) => find_loot_table(structure, name)
+                .ok_or_else(|| format!("{} has no loot table `{name}`", structure.id()))?,
+            None => &structure.loot_tables()[0],
+        };
+        let (target, loot_seed) = match (&data, data.get("Items")) {
+            (Tag::Compound(_), Some(items)) => (LootTarget::from_items(items)?, None),
+            (Tag::Compound(_), None) if data.get("LootTable").is_some() => {
+                // A seed of zero is rolled when the chest is opened.
+                let seed = data
+                    .get("LootTableSeed")
+                    .and_then(Tag::as_i64)
+                    .filter(|&seed| seed != 0)
+                    .ok_or("the unopened chest has no `LootTableSeed` to roll its loot from")?;
+                (
+                    LootTarget::exact(&loot_table.get_loot(seed, true)),
+                    Some(seed),
+                )
+            }
+            (Tag::Compound(_), None) => return Err("the block has no `Items`".to_string()),
+            _ => (LootTarget::from_items(&data)?, None),
+        };
+        let container = match data.get("id").and_then(Tag::as_str) {
+            Some(id) => {
+                Container::from_id(id).ok_or_else(|| format!("unknown container `{id}`"))?
+            }
+            None => Container::Chest,
+        };
+        check_container(structure, container)?;
+        Ok(Self {
+            structure,
+            position: Position::Block(block),
+            container,
+            loot_table,
+            chest_index: 0,
+            start_chunk: None,
+            version: DEFAULT_VERSION,
+            loot_seed,
+            target,
+        })
+    }
+
+    pub const fn chunk(&self) -> ChunkPos {
+        self.position.chunk()
+    }
+
+    /// The chest as its structure generates it, seeded like `version`.
+    pub const fn chest(&self) -> StructureChest {
+        StructureChest::new(self.structure, self.chunk(), self.chest_index)
+            .with_loot_table(self.loot_table)
+            .with_version(self.version)
+    }
+
+    /// The filter this chest puts on the structure seed.
+    pub const fn to_clue(&self) -> Clue {
+        let chest = self.chest();
+        let target = self.target;
+        match self.structure {
+            Structure::BuriedTreasure => Clue::BuriedTreasure { chest, target },
+            Structure::Village => Clue::Village(VillageCracker {
+                start_chunk: self.start_chunk,
+                chest,
+                target,
+            }),
+            _ => Clue::Chest { chest, target },
+        }
+    }
+}
+
+/// Writes the observation in the format `parse` reads, leaving unknown slots out.
+impl fmt::Display for ChestObservation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "structure: {}", self.structure.id())?;
+        match self.position {
+            Position::Chunk(chunk) => writeln!(f, "chunk: {} {}", chunk.x, chunk.z)?,
+            Position::Block(block) => writeln!(f, "block: {} {} {}", block.x, block.y, block.z)?,
+        }
+        writeln!(f, "container: {}", self.container.id())?;
+        writeln!(f, "loot_table: {}", self.loot_table.name)?;
+        writeln!(f, "version: {}", self.version)?;
+        if self.chest_index != 0 {
+            writeln!(f, "index: {}", self.chest_index)?;
+        }
+        if let Some(start) = self.start_chunk {
+            writeln!(f, "start: {} {}", start.x, start.z)?;
+        }
+        if let Some(loot_seed) = self.loot_seed {
+            writeln!(f, "loot_seed: {loot_seed}")?;
+        }
+        for (slot, stack) in self.target.slots.iter().enumerate() {
+            match stack {
+                Some(stack) if stack.is_empty() => writeln!(f, "{slot}: empty")?,
+                Some(stack) => writeln!(
+                    f,
+                    "{slot}: {} x{}",
+                    stack.get_item().id(),
+                    stack.get_count()
+                )?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
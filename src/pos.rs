@@ -0,0 +1,61 @@
+/// Conversions between the position types follow Java's `int` semantics: shifts and
+/// products wrap, divisions round down like `Math.floorDiv`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BlockPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl BlockPos {
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// `x >> 4`, the chunk the block is in.
+    #[inline(always)]
+    pub const fn to_chunk(self) -> ChunkPos {
+        ChunkPos::new(self.x >> 4, self.z >> 4)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct ChunkPos {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl ChunkPos {
+    pub const fn new(x: i32, z: i32) -> Self {
+        Self { x, z }
+    }
+
+    /// `x << 4`, the chunk's corner block at height zero.
+    #[inline(always)]
+    pub const fn get_min_block(self) -> BlockPos {
+        BlockPos::new(self.x.wrapping_shl(4), 0, self.z.wrapping_shl(4))
+    }
+
+    #[inline(always)]
+    pub const fn to_region(self, spacing: i32) -> RegionPos {
+        RegionPos::new(self.x.div_euclid(spacing), self.z.div_euclid(spacing))
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RegionPos {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl RegionPos {
+    pub const fn new(x: i32, z: i32) -> Self {
+        Self { x, z }
+    }
+
+    /// The first chunk of the region.
+    #[inline(always)]
+    pub const fn get_min_chunk(self, spacing: i32) -> ChunkPos {
+        ChunkPos::new(self.x.wrapping_mul(spacing), self.z.wrapping_mul(spacing))
+    }
+}
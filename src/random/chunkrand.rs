@@ -1,66 +1,176 @@
-use std::ops::{Deref, DerefMut};
-
+use crate::pos::{BlockPos, ChunkPos, RegionPos};
 use crate::random::jrand::JRand;
-use crate::random::mcversion::V1_13;
+use crate::random::mcversion::{MCVersion, V1_13, V1_18};
 use crate::random::mth::MASK_48;
 use crate::random::seeds::{PositionSeed, RegionSeed};
-use crate::MC_VERSION;
+use crate::random::xoroshiro::Xoroshiro;
+use crate::DEFAULT_VERSION;
 
+/// A `java.util.Random` carrying the version its seeding formulas follow.
+///
+/// From 1.18 on population and decorator seeding switch to a `Xoroshiro` source, which then
+/// backs `next` until the random is seeded the legacy way again. Like `WorldgenRandom`, only
+/// `next` looks at the source, every `get_next_*` is `java.util.Random`'s on top of it.
 #[derive(Debug)]
 pub struct ChunkRand {
     jrand: JRand,
-}
-
-impl Deref for ChunkRand {
-    type Target = JRand;
-
-    fn deref(&self) -> &Self::Target {
-        &self.jrand
-    }
-}
-
-impl DerefMut for ChunkRand {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.jrand
-    }
+    xoroshiro: Xoroshiro,
+    use_xoroshiro: bool,
+    version: MCVersion,
 }
 
 impl Default for ChunkRand {
     fn default() -> Self {
-        Self {
-            jrand: JRand::new_scramble(0, false),
-        }
+        Self::new_scramble(0, false)
     }
 }
 
 impl From<JRand> for ChunkRand {
     fn from(jrand: JRand) -> Self {
-        Self { jrand }
+        Self {
+            jrand,
+            xoroshiro: Xoroshiro::from_seed_128(0, 0),
+            use_xoroshiro: false,
+            version: DEFAULT_VERSION,
+        }
     }
 }
 
 impl ChunkRand {
     pub const fn new(seed: i64) -> Self {
-        Self {
-            jrand: JRand::new(seed),
-        }
+        Self::new_scramble(seed, true)
     }
 
     pub const fn new_scramble(seed: i64, scramble: bool) -> Self {
         Self {
             jrand: JRand::new_scramble(seed, scramble),
+            xoroshiro: Xoroshiro::from_seed_128(0, 0),
+            use_xoroshiro: false,
+            version: DEFAULT_VERSION,
+        }
+    }
+
+    pub const fn with_version(self, version: MCVersion) -> Self {
+        Self { version, ..self }
+    }
+
+    pub const fn get_version(&self) -> &MCVersion {
+        &self.version
+    }
+
+    pub const fn set_version(&mut self, version: MCVersion) {
+        self.version = version;
+    }
+
+    #[inline(always)]
+    pub const fn set_seed(&mut self, seed: i64, scramble: bool) {
+        self.use_xoroshiro = false;
+        self.jrand.set_seed(seed, scramble);
+    }
+
+    /// `WorldgenRandom.setSeed` on a `XoroshiroRandomSource`, the full 64 bits are kept.
+    #[inline(always)]
+    pub const fn set_xoroshiro_seed(&mut self, seed: i64) {
+        self.use_xoroshiro = true;
+        self.xoroshiro.set_seed(seed);
+    }
+
+    #[inline(always)]
+    const fn uses_xoroshiro_worldgen(&self) -> bool {
+        self.version.is_newer_or_equal_to(&V1_18)
+    }
+
+    /// `WorldgenRandom.next`: a non-legacy source hands out the top `bits` of a `nextLong`.
+    #[inline(always)]
+    pub const fn next(&mut self, bits: i32) -> i32 {
+        if self.use_xoroshiro {
+            (self.xoroshiro.get_next_long() as u64 >> (64 - bits)) as i32
+        } else {
+            self.jrand.next(bits)
+        }
+    }
+
+    #[inline(always)]
+    pub const fn get_next_int(&mut self) -> i32 {
+        self.next(32)
+    }
+
+    #[inline(always)]
+    pub const fn get_next_long(&mut self) -> i64 {
+        ((self.next(32) as i64) << 32) + self.next(32) as i64
+    }
+
+    #[inline(always)]
+    pub const fn get_next_int_bound(&mut self, n: i32) -> i32 {
+        let m = n - 1;
+
+        debug_assert!(n >= 0);
+
+        if (m & n) == 0 {
+            let x = n as i64 * self.next(31) as i64;
+            return (x >> 31) as i32;
+        }
+
+        let mut bits: i32;
+        let mut value: i32;
+
+        loop {
+            bits = self.next(31);
+            value = bits % n;
+            if bits - value + m >= 0 {
+                break;
+            }
+        }
+
+        value
+    }
+
+    #[inline(always)]
+    pub const fn get_next_bool(&mut self) -> bool {
+        self.next(1) != 0
+    }
+
+    #[inline(always)]
+    pub const fn get_next_float(&mut self) -> f32 {
+        self.next(24) as f32 / (1 << 24) as f32
+    }
+
+    #[inline(always)]
+    pub const fn get_next_double(&mut self) -> f64 {
+        (((self.next(26) as i64) << 27) + self.next(27) as i64) as f64 / (1_i64 << 53) as f64
+    }
+
+    #[inline(always)]
+    pub fn shuffle<T>(&mut self, vec: &mut [T]) {
+        let size = vec.len();
+        for i in (2..=size).rev() {
+            vec.swap(i - 1, self.get_next_int_bound(i as i32) as usize);
         }
     }
 
-    pub fn set_terrain_seed(&mut self, chunk_x: i32, chunk_z: i32) -> i64 {
-        let seed = chunk_x as i64 * 341873128712 + chunk_z as i64 * 132897987541;
+    pub const fn set_terrain_seed(&mut self, chunk: ChunkPos) -> i64 {
+        let seed = chunk.x as i64 * 341873128712 + chunk.z as i64 * 132897987541;
         self.set_seed(seed, true);
         seed & MASK_48
     }
 
-    pub fn set_population_seed(&mut self, world_seed: i64, x: i32, z: i32) -> i64 {
+    /// `setDecorationSeed` for the chunk whose min block is `block`. Before 1.13 the chunk
+    /// coordinates go into the formula instead of the block ones.
+    pub const fn set_population_seed(&mut self, world_seed: i64, block: BlockPos) -> i64 {
+        let (x, z) = self.population_coords(block);
+        if self.uses_xoroshiro_worldgen() {
+            self.set_xoroshiro_seed(world_seed);
+            let a = self.get_next_long() | 1;
+            let b = self.get_next_long() | 1;
+            let f = (x as i64).wrapping_mul(a);
+            let g = (z as i64).wrapping_mul(b);
+            let seed = f.wrapping_add(g) ^ world_seed;
+            self.set_xoroshiro_seed(seed);
+            return seed;
+        }
+
         self.set_seed(world_seed, true);
-        let (a, b) = if MC_VERSION.is_older_than(&V1_13) {
+        let (a, b) = if self.version.is_older_than(&V1_13) {
             let a = (self.get_next_long() / 2) * 2 + 1;
             let b = self.get_next_long() / 2 * 2 + 1;
             (a, b)
@@ -70,15 +180,15 @@ impl ChunkRand {
             (a, b)
         };
 
-        let f = x as i64 * a;
-        let g = z as i64 * b;
+        let f = (x as i64).wrapping_mul(a);
+        let g = (z as i64).wrapping_mul(b);
 
-        let seed = (f + g) ^ world_seed;
+        let seed = f.wrapping_add(g) ^ world_seed;
         self.set_seed(seed, true);
         seed & MASK_48
     }
 
-    pub fn set_decorator_seed_with_index(
+    pub const fn set_decorator_seed_with_index(
         &mut self,
         population_seed: i64,
         index: i32,
@@ -87,104 +197,111 @@ impl ChunkRand {
         self.set_decorator_seed(population_seed, index + 10000 * step)
     }
 
-    pub fn set_decorator_seed(&mut self, population_seed: i64, salt: i32) -> i64 {
-        if MC_VERSION.is_older_than(&V1_13) {
-            panic!("Unsupported version: {}", MC_VERSION)
+    /// Before 1.13 there is no per feature salt and every decorator keeps drawing from the
+    /// population random in order, so `salt` is ignored and the random is put back at the
+    /// start of population: the caller has to skip the draws of the decorators before.
+    pub const fn set_decorator_seed(&mut self, population_seed: i64, salt: i32) -> i64 {
+        if self.version.is_older_than(&V1_13) {
+            self.set_seed(population_seed, true);
+            return population_seed & MASK_48;
         }
 
         let seed = population_seed + salt as i64;
+        if self.uses_xoroshiro_worldgen() {
+            self.set_xoroshiro_seed(seed);
+            return seed;
+        }
         self.set_seed(seed, true);
         seed & MASK_48
     }
 
-    pub fn set_decorator_seed_block(
+    #[inline(always)]
+    const fn population_coords(&self, block: BlockPos) -> (i32, i32) {
+        if self.version.is_older_than(&V1_13) {
+            let chunk = block.to_chunk();
+            (chunk.x, chunk.z)
+        } else {
+            (block.x, block.z)
+        }
+    }
+
+    pub const fn set_decorator_seed_block(
         &mut self,
         world_seed: i64,
-        block_x: i32,
-        block_z: i32,
+        block: BlockPos,
         index: i32,
         step: i32,
     ) -> i64 {
-        let population_seed = self.set_population_seed(world_seed, block_x, block_z);
+        let population_seed = self.set_population_seed(world_seed, block);
         self.set_decorator_seed_with_index(population_seed, index, step)
     }
 
-    pub fn set_decorator_seed_block_salt(
+    pub const fn set_decorator_seed_block_salt(
         &mut self,
         world_seed: i64,
-        block_x: i32,
-        block_z: i32,
+        block: BlockPos,
         salt: i32,
     ) -> i64 {
-        let population_seed = self.set_population_seed(world_seed, block_x, block_z);
+        let population_seed = self.set_population_seed(world_seed, block);
         self.set_decorator_seed(population_seed, salt)
     }
 
-    pub fn set_carver_seed(&mut self, world_seed: i64, chunk_x: i32, chunk_z: i32) -> i64 {
+    pub const fn set_carver_seed(&mut self, world_seed: i64, chunk: ChunkPos) -> i64 {
         self.set_seed(world_seed, true);
         let a = self.get_next_long();
         let b = self.get_next_long();
-        let seed = (chunk_x as i64 * a) ^ (chunk_z as i64 * b) ^ world_seed;
+        let seed = (chunk.x as i64 * a) ^ (chunk.z as i64 * b) ^ world_seed;
         self.set_seed(seed, true);
         seed & MASK_48
     }
 
-    pub fn set_region_seed(
-        &mut self,
-        world_seed: i64,
-        region_x: i32,
-        region_z: i32,
-        salt: i32,
-    ) -> i64 {
-        let seed = region_x as i64 * RegionSeed::A
-            + region_z as i64 * RegionSeed::B
+    pub const fn set_region_seed(&mut self, world_seed: i64, region: RegionPos, salt: i32) -> i64 {
+        let seed = region.x as i64 * RegionSeed::A
+            + region.z as i64 * RegionSeed::B
             + world_seed
             + salt as i64;
         self.set_seed(seed, true);
         seed & MASK_48
     }
 
-    pub fn set_weak_seed(&mut self, world_seed: i64, chunk_x: i32, chunk_z: i32) -> i64 {
-        let sx = chunk_x >> 4;
-        let sz = chunk_z >> 4;
+    pub const fn set_weak_seed(&mut self, world_seed: i64, chunk: ChunkPos) -> i64 {
+        let sx = chunk.x >> 4;
+        let sz = chunk.z >> 4;
         let seed = (sx ^ sz << 4) as i64 ^ world_seed;
         self.set_seed(seed, true);
         seed & MASK_48
     }
 
-    pub fn set_slime_seed_scramble(
+    pub const fn set_slime_seed_scramble(
         &mut self,
         world_seed: i64,
-        chunk_x: i32,
-        chunk_z: i32,
+        chunk: ChunkPos,
         scrambler: i64,
     ) -> i64 {
+        // The products are Java `int`s and wrap before being widened.
+        let (x, z) = (chunk.x, chunk.z);
         let seed = (world_seed
-            + (chunk_x * chunk_x * 4987142) as i64
-            + (chunk_x * 5947611) as i64
-            + (chunk_z * chunk_z) as i64 * 4392871
-            + (chunk_z * 389711) as i64)
+            + x.wrapping_mul(x).wrapping_mul(4987142) as i64
+            + x.wrapping_mul(5947611) as i64
+            + z.wrapping_mul(z) as i64 * 4392871
+            + z.wrapping_mul(389711) as i64)
             ^ scrambler;
         self.set_seed(seed, true);
         seed & MASK_48
     }
 
-    pub fn set_slime_seed(&mut self, world_seed: i64, chunk_x: i32, chunk_z: i32) -> i64 {
-        self.set_slime_seed_scramble(world_seed, chunk_x, chunk_z, 987234911)
+    pub const fn set_slime_seed(&mut self, world_seed: i64, chunk: ChunkPos) -> i64 {
+        self.set_slime_seed_scramble(world_seed, chunk, 987234911)
     }
 
-    pub fn set_position_seed(&mut self, x: i32, y: i32, z: i32) -> i64 {
-        let seed = PositionSeed::get_position_seed(x, y, z);
+    pub const fn set_position_seed(&mut self, pos: BlockPos) -> i64 {
+        let seed = PositionSeed::get_position_seed(pos);
         self.set_seed(seed, true);
         seed & MASK_48
     }
 
-    pub fn set_base_stone_seed(&mut self, world_seed: i64, x: i32, y: i32, z: i32) -> i64 {
-        self.set_seed(world_seed, true);
-        let a = self.get_next_long();
-        let b = self.get_next_long();
-        let _c = self.get_next_long();
-        let seed = (x as i64 * a) ^ (y as i64 * b) ^ (z as i64 * z as i64) ^ world_seed;
+    pub const fn set_base_stone_seed(&mut self, world_seed: i64, pos: BlockPos) -> i64 {
+        let seed = PositionSeed::get_base_stone_seed(world_seed, pos);
         self.set_seed(seed, true);
         seed
     }
@@ -193,7 +310,7 @@ impl ChunkRand {
         list[self.get_next_int_bound(list.len() as i32) as usize].clone()
     }
 
-    pub fn get_int(&mut self, min: i32, max: i32) -> i32 {
+    pub const fn get_int(&mut self, min: i32, max: i32) -> i32 {
         if min >= max {
             min
         } else {
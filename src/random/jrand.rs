@@ -152,17 +152,17 @@ impl JRand {
     }
 
     #[inline(always)]
-    pub fn next_seed(&mut self) -> i64 {
+    pub const fn next_seed(&mut self) -> i64 {
         let next = self.lcg.next_seed(self.seed);
         self.seed = next;
         next
     }
 
-    pub fn advance_calls(&mut self, calls: i64) {
+    pub const fn advance_calls(&mut self, calls: i64) {
         self.advance(self.lcg.combine_steps(calls))
     }
 
-    fn advance(&mut self, skip: LCG) {
+    const fn advance(&mut self, skip: LCG) {
         self.seed = skip.next_seed(self.seed);
     }
 }
@@ -280,26 +280,11 @@ impl LCG {
         )
     }
 
+    /// One step of this LCG. It used to step `JAVA` whatever `self` was, so a combined skip
+    /// such as `combine_steps(n)` only ever moved one call ahead.
     #[inline(always)]
-    pub const fn next_seed(&self, mut seed: i64) -> i64 {
-        let mut m = 1;
-        let mut a = 0;
-        let mut im = Self::JAVA.multiplier;
-        let mut ia = Self::JAVA.addend;
-        let mut k = 1;
-
-        while k != 0 {
-            if (k & 1) != 0 {
-                m *= im;
-                a = im * a + ia;
-            }
-            ia *= im + 1;
-            im *= im;
-            k >>= 1;
-        }
-
-        seed = seed * m + a;
-        seed & MASK_48
+    pub const fn next_seed(&self, seed: i64) -> i64 {
+        self.mod_(seed.wrapping_mul(self.multiplier).wrapping_add(self.addend))
     }
 
     #[inline(always)]
@@ -3,11 +3,34 @@ use std::collections::HashMap;
 
 use lazy_static::lazy_static;
 
+/// Where a version sits in the development cycle of the release it leads up to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Snapshot { year: u8, week: u8, letter: u8 },
+    PreRelease(u8),
+    ReleaseCandidate(u8),
+    Release,
+}
+
+impl Stage {
+    const fn order_key(&self) -> i32 {
+        match *self {
+            Self::Snapshot { year, week, letter } => {
+                (year as i32 * 100 + week as i32) * 26 + (letter - b'a') as i32
+            }
+            Self::PreRelease(n) => 1_000_000 + n as i32,
+            Self::ReleaseCandidate(n) => 2_000_000 + n as i32,
+            Self::Release => 3_000_000,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct MCVersion {
     name: &'static str,
     release: i32,
     sub_version: i32,
+    stage: Stage,
 }
 
 impl MCVersion {
@@ -16,9 +39,14 @@ impl MCVersion {
             name,
             release,
             sub_version,
+            stage: Stage::Release,
         }
     }
 
+    const fn with_stage(&self, stage: Stage) -> Self {
+        Self { stage, ..*self }
+    }
+
     #[inline]
     pub const fn const_cmp(&self, other: &Self) -> Ordering {
         if self.release > other.release {
@@ -29,6 +57,10 @@ impl MCVersion {
             Ordering::Greater
         } else if self.sub_version < other.sub_version {
             Ordering::Less
+        } else if self.stage.order_key() > other.stage.order_key() {
+            Ordering::Greater
+        } else if self.stage.order_key() < other.stage.order_key() {
+            Ordering::Less
         } else {
             Ordering::Equal
         }
@@ -38,6 +70,36 @@ impl MCVersion {
         VERSIONS_MAP.get(name).cloned()
     }
 
+    /// Like `from_string`, but also accepts snapshots (`23w31a`), pre-releases
+    /// (`1.20-pre1`, `1.20 Pre-release 1`) and release candidates (`1.20-rc1`), which are
+    /// ordered just before the release they lead up to.
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if let Some(version) = Self::from_string(name) {
+            return Some(*version);
+        }
+        if let Some(stage) = parse_snapshot(name) {
+            return snapshot_target(&stage).map(|version| version.with_stage(stage));
+        }
+
+        let (release, stage) = if let Some((release, n)) = name.split_once("-pre") {
+            (release, Stage::PreRelease(n.parse().ok()?))
+        } else if let Some((release, n)) = name.split_once(" Pre-release ") {
+            (release, Stage::PreRelease(n.parse().ok()?))
+        } else if let Some((release, n)) = name.split_once("-rc") {
+            (release, Stage::ReleaseCandidate(n.parse().ok()?))
+        } else if let Some((release, n)) = name.split_once(" Release Candidate ") {
+            (release, Stage::ReleaseCandidate(n.parse().ok()?))
+        } else {
+            return None;
+        };
+        Self::from_string(release).map(|version| version.with_stage(stage))
+    }
+
+    pub const fn get_stage(&self) -> Stage {
+        self.stage
+    }
+
     pub const fn get_release(&self) -> i32 {
         self.release
     }
@@ -86,6 +148,13 @@ impl MCVersion {
         VERSIONS
             .iter()
             .position(|v| v.name == self.name)
+            .map(|i| {
+                if matches!(self.stage, Stage::Release) {
+                    i
+                } else {
+                    i + 1
+                }
+            })
             .and_then(|i| if i > 0 { Some(&VERSIONS[i - 1]) } else { None })
     }
 
@@ -128,19 +197,105 @@ impl PartialOrd for MCVersion {
 }
 impl Ord for MCVersion {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.release
-            .cmp(&other.release)
-            .then_with(|| other.sub_version.cmp(&self.sub_version))
+        self.const_cmp(other)
     }
 }
 
 impl std::fmt::Display for MCVersion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.name)
+        match self.stage {
+            Stage::Snapshot { year, week, letter } => {
+                write!(f, "{:02}w{:02}{}", year, week, letter as char)
+            }
+            Stage::PreRelease(n) => write!(f, "{}-pre{}", self.name, n),
+            Stage::ReleaseCandidate(n) => write!(f, "{}-rc{}", self.name, n),
+            Stage::Release => write!(f, "{}", self.name),
+        }
+    }
+}
+
+fn parse_snapshot(name: &str) -> Option<Stage> {
+    let (year, rest) = name.split_once('w')?;
+    // Checked before slicing, a multi-byte last character has no byte boundary there.
+    let letter = *rest
+        .as_bytes()
+        .last()
+        .filter(|letter| letter.is_ascii_lowercase())?;
+    let week = &rest[..rest.len() - 1];
+    if year.len() != 2 || week.len() != 2 {
+        return None;
+    }
+    Some(Stage::Snapshot {
+        year: year.parse().ok()?,
+        week: week.parse().ok()?,
+        letter,
+    })
+}
+
+/// The last snapshot week (`year * 100 + week`) of each release's development cycle.
+const SNAPSHOT_TARGETS: &[(i32, MCVersion)] = &[
+    (1822, V1_13),
+    (1833, V1_13_1),
+    (1914, V1_14),
+    (1946, V1_15),
+    (2022, V1_16),
+    (2030, V1_16_2),
+    (2120, V1_17),
+    (2144, V1_18),
+    (2219, V1_19),
+    (2224, V1_19_1),
+    (2246, V1_19_3),
+    (2307, V1_19_4),
+    (2318, V1_20),
+    (2335, V1_20_2),
+    (2346, V1_20_3),
+    (2414, V1_20_5),
+    (2421, V1_21),
+    (2440, V1_21_2),
+    (2446, V1_21_4),
+    (2510, V1_21_5),
+    (2521, V1_21_6),
+    (2537, V1_21_9),
+];
+
+/// Snapshots from the 1.13 cycle (`17w43a`) on map to the release they preceded.
+fn snapshot_target(stage: &Stage) -> Option<&'static MCVersion> {
+    let Stage::Snapshot { year, week, .. } = *stage else {
+        return None;
+    };
+    let year_week = year as i32 * 100 + week as i32;
+    if year_week < 1743 {
+        return None;
     }
+    SNAPSHOT_TARGETS
+        .iter()
+        .find(|(last_week, _)| year_week <= *last_week)
+        .map(|(_, version)| version)
 }
 
 // Define all versions as static constants
+pub const V1_21_10: MCVersion = MCVersion::new("1.21.10", 21, 10); // October 7, 2025
+pub const V1_21_9: MCVersion = MCVersion::new("1.21.9", 21, 9); // September 30, 2025
+pub const V1_21_8: MCVersion = MCVersion::new("1.21.8", 21, 8); // July 17, 2025
+pub const V1_21_7: MCVersion = MCVersion::new("1.21.7", 21, 7); // June 30, 2025
+pub const V1_21_6: MCVersion = MCVersion::new("1.21.6", 21, 6); // June 17, 2025
+pub const V1_21_5: MCVersion = MCVersion::new("1.21.5", 21, 5); // March 25, 2025
+pub const V1_21_4: MCVersion = MCVersion::new("1.21.4", 21, 4); // December 3, 2024
+pub const V1_21_3: MCVersion = MCVersion::new("1.21.3", 21, 3); // October 23, 2024
+pub const V1_21_2: MCVersion = MCVersion::new("1.21.2", 21, 2); // October 22, 2024
+pub const V1_21_1: MCVersion = MCVersion::new("1.21.1", 21, 1); // August 8, 2024
+pub const V1_21: MCVersion = MCVersion::new("1.21", 21, 0); // June 13, 2024
+
+pub const V1_20_6: MCVersion = MCVersion::new("1.20.6", 20, 6); // April 29, 2024
+pub const V1_20_5: MCVersion = MCVersion::new("1.20.5", 20, 5); // April 23, 2024
+pub const V1_20_4: MCVersion = MCVersion::new("1.20.4", 20, 4); // December 7, 2023
+pub const V1_20_3: MCVersion = MCVersion::new("1.20.3", 20, 3); // December 5, 2023
+pub const V1_20_2: MCVersion = MCVersion::new("1.20.2", 20, 2); // September 21, 2023
+pub const V1_20_1: MCVersion = MCVersion::new("1.20.1", 20, 1); // June 12, 2023
+pub const V1_20: MCVersion = MCVersion::new("1.20", 20, 0); // June 7, 2023
+
+pub const V1_19_4: MCVersion = MCVersion::new("1.19.4", 19, 4); // March 14, 2023
+pub const V1_19_3: MCVersion = MCVersion::new("1.19.3", 19, 3); // December 7, 2022
 pub const V1_19_2: MCVersion = MCVersion::new("1.19.2", 19, 2); // August 5, 2022
 pub const V1_19_1: MCVersion = MCVersion::new("1.19.1", 19, 1); // July 27, 2022
 pub const V1_19: MCVersion = MCVersion::new("1.19", 19, 0); // June 7, 2022
@@ -310,6 +465,26 @@ pub const VA1_0_5_01: MCVersion = MCVersion::new("a1.0.5_01", 0, 501); // July 1
 pub const VA1_0_4: MCVersion = MCVersion::new("a1.0.4", 0, 4); // July 8, 2010
 
 pub const VERSIONS: &[MCVersion] = &[
+    V1_21_10,
+    V1_21_9,
+    V1_21_8,
+    V1_21_7,
+    V1_21_6,
+    V1_21_5,
+    V1_21_4,
+    V1_21_3,
+    V1_21_2,
+    V1_21_1,
+    V1_21,
+    V1_20_6,
+    V1_20_5,
+    V1_20_4,
+    V1_20_3,
+    V1_20_2,
+    V1_20_1,
+    V1_20,
+    V1_19_4,
+    V1_19_3,
     V1_19_2,
     V1_19_1,
     V1_19,
@@ -2,4 +2,6 @@ pub mod chunkrand;
 pub mod jrand;
 pub mod mcversion;
 pub mod mth;
+pub mod reverse;
 pub mod seeds;
+pub mod xoroshiro;
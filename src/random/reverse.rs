@@ -0,0 +1,269 @@
+use crate::random::jrand::{JRand, LCG};
+use crate::random::mth::MASK_48;
+
+/// One call made on a `java.util.Random`, in the order the game makes them.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Call {
+    /// `nextInt(bound)` returned `value`.
+    NextInt { bound: i32, value: i32 },
+    /// `nextInt(bound)` whose result was not seen. It can still be rejected and retried.
+    NextIntUnknown { bound: i32 },
+    /// `nextFloat()` returned something in `min..max`.
+    NextFloat { min: f32, max: f32 },
+    /// Any number of `next(bits)` calls whose results are not constrained.
+    Skip(u32),
+}
+
+/// What a single observed call says about the 48 bit state it was drawn from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StateConstraint {
+    /// `min <= state <= max`.
+    Interval { min: i64, max: i64 },
+    /// `(state >> 17) % modulus == residue` with `state >> 17 <= max_bits`, the draws
+    /// `nextInt` keeps for a bound that is not a power of two.
+    Residue {
+        modulus: i64,
+        residue: i64,
+        max_bits: i64,
+    },
+}
+
+impl StateConstraint {
+    /// The constraint a `nextInt(bound)` returning `value` puts on its accepted draw.
+    pub const fn next_int(bound: i32, value: i32) -> Option<Self> {
+        if bound <= 0 || value < 0 || value >= bound {
+            return None;
+        }
+        let bound = bound as i64;
+        let value = value as i64;
+
+        if bound & (bound - 1) == 0 {
+            // `(bound * next(31)) >> 31` keeps the top bits of the draw.
+            let shift = 48 - bound.trailing_zeros() as i64;
+            return Some(Self::Interval {
+                min: value << shift,
+                max: ((value + 1) << shift) - 1,
+            });
+        }
+
+        // `bits - bits % bound + bound - 1` overflows an int exactly for the last,
+        // incomplete run of residues.
+        Some(Self::Residue {
+            modulus: bound,
+            residue: value,
+            max_bits: (1 << 31) - (1 << 31) % bound - 1,
+        })
+    }
+
+    /// The constraint a `nextFloat` in `min..max` puts on its draw.
+    pub fn next_float(min: f32, max: f32) -> Option<Self> {
+        const UNIT: f64 = (1 << 24) as f64;
+        let low = ((min.max(0.0) as f64) * UNIT).ceil() as i64;
+        let high = ((max.min(1.0) as f64) * UNIT).ceil() as i64 - 1;
+        if low > high {
+            return None;
+        }
+        Some(Self::Interval {
+            min: low << 24,
+            max: (high << 24) | ((1 << 24) - 1),
+        })
+    }
+
+    pub const fn matches(&self, state: i64) -> bool {
+        match *self {
+            Self::Interval { min, max } => min <= state && state <= max,
+            Self::Residue {
+                modulus,
+                residue,
+                max_bits,
+            } => {
+                let bits = state >> 17;
+                bits <= max_bits && bits % modulus == residue
+            }
+        }
+    }
+
+    /// How many 48 bit states satisfy the constraint.
+    pub const fn candidate_count(&self) -> u64 {
+        match *self {
+            Self::Interval { min, max } => (max - min + 1) as u64,
+            Self::Residue {
+                modulus,
+                residue,
+                max_bits,
+            } => {
+                if residue > max_bits {
+                    0
+                } else {
+                    (((max_bits - residue) / modulus + 1) as u64) << 17
+                }
+            }
+        }
+    }
+
+    /// Every state satisfying the constraint, in increasing order.
+    pub fn states(&self) -> Box<dyn Iterator<Item = i64>> {
+        match *self {
+            Self::Interval { min, max } => Box::new(min..=max),
+            Self::Residue {
+                modulus,
+                residue,
+                max_bits,
+            } => Box::new(
+                (residue..=max_bits)
+                    .step_by(modulus as usize)
+                    .flat_map(|bits| (bits << 17)..((bits + 1) << 17)),
+            ),
+        }
+    }
+}
+
+/// Candidates `solve` enumerates before giving up, about a minute on one thread.
+pub const DEFAULT_CANDIDATE_LIMIT: u64 = 1 << 32;
+
+/// Recovers the internal seeds of a `java.util.Random` from a sequence of calls.
+///
+/// Each observed call is turned into a constraint on the state it reads, assuming no
+/// earlier `nextInt` was rejected. The most selective one is enumerated and stepped back to
+/// the starting seed, then every candidate is replayed through the real calls, so retried
+/// draws shift later calls exactly like they do in game.
+///
+/// Only that one constraint is enumerated, so a weak driver like `nextInt(16)` still leaves
+/// `2^44` states. `solve` refuses to start above the candidate limit instead of running
+/// for days.
+pub struct ConstraintSolver {
+    calls: Vec<Call>,
+    candidate_limit: u64,
+}
+
+impl ConstraintSolver {
+    pub const fn new(calls: Vec<Call>) -> Self {
+        Self {
+            calls,
+            candidate_limit: DEFAULT_CANDIDATE_LIMIT,
+        }
+    }
+
+    pub const fn with_candidate_limit(mut self, candidate_limit: u64) -> Self {
+        self.candidate_limit = candidate_limit;
+        self
+    }
+
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// The constraints of the observed calls, keyed by the number of `next` calls made
+    /// up to and including the one they constrain, when nothing gets rejected.
+    pub fn constraints(&self) -> Vec<(i64, StateConstraint)> {
+        let mut step = 0;
+        let mut constraints = Vec::new();
+        for call in &self.calls {
+            match *call {
+                Call::NextInt { bound, value } => {
+                    step += 1;
+                    if let Some(constraint) = StateConstraint::next_int(bound, value) {
+                        constraints.push((step, constraint));
+                    }
+                }
+                Call::NextIntUnknown { .. } => step += 1,
+                Call::NextFloat { min, max } => {
+                    step += 1;
+                    if let Some(constraint) = StateConstraint::next_float(min, max) {
+                        constraints.push((step, constraint));
+                    }
+                }
+                Call::Skip(count) => step += count as i64,
+            }
+        }
+        constraints
+    }
+
+    /// Whether the calls made from this internal (already scrambled) seed give back every
+    /// observation.
+    pub fn check(&self, internal_seed: i64) -> bool {
+        let mut rand = JRand::new_scramble(internal_seed, false);
+        self.calls.iter().all(|call| match *call {
+            Call::NextInt { bound, value } => rand.get_next_int_bound(bound) == value,
+            Call::NextIntUnknown { bound } => {
+                rand.get_next_int_bound(bound);
+                true
+            }
+            Call::NextFloat { min, max } => {
+                let value = rand.get_next_float();
+                min <= value && value < max
+            }
+            Call::Skip(count) => {
+                (0..count).for_each(|_| {
+                    rand.next(32);
+                });
+                true
+            }
+        })
+    }
+
+    /// All internal seeds agreeing with the calls when at most `max_rejections` draws were
+    /// thrown away before the most selective observation. A rejection in the first call
+    /// also lets the seed one draw later replay the same calls, so both are returned.
+    ///
+    /// Fails when no call constrains the state, or when the driver would need more
+    /// candidates than the limit.
+    pub fn solve(&self, max_rejections: u32) -> Result<Vec<i64>, String> {
+        let Some((step, driver)) = self
+            .constraints()
+            .into_iter()
+            .min_by_key(|(_, constraint)| constraint.candidate_count())
+        else {
+            return Err("none of the calls constrains the seed".to_string());
+        };
+        let candidates = driver
+            .candidate_count()
+            .saturating_mul(max_rejections as u64 + 1);
+        if candidates > self.candidate_limit {
+            return Err(format!(
+                "the most selective call leaves {candidates} candidates, over the limit of {}",
+                self.candidate_limit
+            ));
+        }
+
+        let mut seeds = Vec::new();
+        for rejections in 0..=max_rejections as i64 {
+            let back = LCG::JAVA.combine_steps(-(step + rejections));
+            seeds.extend(
+                driver
+                    .states()
+                    .map(|state| back.next_seed(state))
+                    .filter(|&seed| self.check(seed)),
+            );
+        }
+        seeds.sort_unstable();
+        seeds.dedup();
+        Ok(seeds)
+    }
+
+    /// The seeds `new Random(seed)` would have been given to end up at each solution.
+    pub fn solve_scrambled(&self, max_rejections: u32) -> Result<Vec<i64>, String> {
+        Ok(self
+            .solve(max_rejections)?
+            .into_iter()
+            .map(|seed| (seed ^ 0x5deece66d) & MASK_48)
+            .collect())
+    }
+}
+
+/// The internal seeds a `java.util.Random` can be at right before a `nextLong()` that
+/// returns `value`.
+///
+/// Its first `next(32)` gives the top 32 bits of the state it steps to,
+/// which leaves 2^16 states to try against the second. Only one long in 65536 can come
+/// out of `nextLong` at all, so most values have none.
+pub fn next_long_states(value: i64) -> Vec<i64> {
+    let low = value as i32;
+    let high = (value.wrapping_sub(low as i64) >> 32) & 0xffff_ffff;
+    let back = LCG::JAVA.invert();
+    (0..1 << 16)
+        .map(|bits| (high << 16) | bits)
+        .filter(|&state| (LCG::JAVA.next_seed(state) >> 16) as i32 == low)
+        .map(|state| back.next_seed(state))
+        .collect()
+}
@@ -1,6 +1,8 @@
-use std::sync::Mutex;
-
+use crate::pos::{BlockPos, RegionPos};
 use crate::random::chunkrand::ChunkRand;
+use crate::random::jrand::JRand;
+use crate::random::mcversion::V1_16_5;
+use crate::random::mth::{MASK_16, MASK_48};
 
 pub struct RegionSeed;
 
@@ -12,8 +14,8 @@ impl RegionSeed {
         Self::A * d_region_x as i64 + Self::B * d_region_z as i64
     }
 
-    pub const fn convert_to_world_seed(region_seed: i64, region_x: i32, region_z: i32, salt: i32) -> i64 {
-        Self::translate(region_seed, region_x, region_z) - salt as i64
+    pub const fn convert_to_world_seed(region_seed: i64, region: RegionPos, salt: i32) -> i64 {
+        Self::translate(region_seed, region.x, region.z) - salt as i64
     }
 
     pub const fn translate(region_seed: i64, d_region_x: i32, d_region_z: i32) -> i64 {
@@ -46,19 +48,88 @@ impl RegionSeed {
     }
 }
 
-pub struct PositionSeed;
+pub struct StructureSeed;
+
+impl StructureSeed {
+    pub const fn to_world_seed(structure_seed: i64, upper_bits: i32) -> i64 {
+        ((upper_bits as i64 & MASK_16 as i64) << 48) | (structure_seed & MASK_48)
+    }
+
+    pub fn get_world_seeds(structure_seed: i64) -> impl Iterator<Item = i64> {
+        (0..=MASK_16).map(move |upper_bits| Self::to_world_seed(structure_seed, upper_bits))
+    }
+}
+
+pub struct PopulationSeed;
 
-static INTERNAL: Mutex<ChunkRand> = Mutex::new(ChunkRand::new_scramble(0, false));
+impl PopulationSeed {
+    /// The structure seeds that give `population_seed` at `block` from 1.13 to 1.17. Bit
+    /// `k` of a population seed only depends on the lowest `17 + k` bits of the structure
+    /// seed while `k < 32`, so those are lifted one bit at a time from every possible
+    /// lowest 16 bits, which keeps about 2^16 candidates at each step.
+    pub fn reverse(population_seed: i64, block: BlockPos) -> Vec<i64> {
+        let population_seed = population_seed & MASK_48;
+        let mut rand = ChunkRand::default().with_version(V1_16_5);
+        let mut seeds: Vec<i64> = (0..=MASK_16 as i64).collect();
+        for bit in 0..32 {
+            let mask = (1_i64 << (bit + 1)) - 1;
+            seeds = seeds
+                .into_iter()
+                .flat_map(|seed| [seed, seed | 1 << (16 + bit)])
+                .filter(|&seed| {
+                    (rand.set_population_seed(seed, block) ^ population_seed) & mask == 0
+                })
+                .collect();
+        }
+        seeds.retain(|&seed| rand.set_population_seed(seed, block) == population_seed);
+        seeds
+    }
+}
+
+pub struct PositionSeed;
 
 impl PositionSeed {
-    pub const fn get_position_seed(x: i32, y: i32, z: i32) -> i64 {
-        let mut i = (x * 3129871) as i64 ^ (z as i64 * 116129781) ^ y as i64;
-        i = i * i * 42317861 + i * 11;
+    /// `Mth.getSeed`: `x * 3129871` is an `int` product that wraps before being widened,
+    /// the rest is `long` arithmetic.
+    pub const fn get_position_seed(pos: BlockPos) -> i64 {
+        let mut i = pos.x.wrapping_mul(3129871) as i64
+            ^ (pos.z as i64).wrapping_mul(116129781)
+            ^ pos.y as i64;
+        i = i
+            .wrapping_mul(i)
+            .wrapping_mul(42317861)
+            .wrapping_add(i.wrapping_mul(11));
         i >> 16
     }
 
-    pub fn get_base_stone_seed(world_seed: i64, x: i32, y: i32, z: i32) -> i64 {
-        let mut chunkrand = INTERNAL.lock().unwrap();
-        chunkrand.set_base_stone_seed(world_seed, x, y, z)
+    /// The three `nextLong`s the base stone seed mixes the coordinates with. They only
+    /// depend on the world seed, so scans can compute them once per seed.
+    #[inline(always)]
+    pub const fn get_base_stone_multipliers(world_seed: i64) -> [i64; 3] {
+        let mut rand = JRand::new(world_seed);
+        [
+            rand.get_next_long(),
+            rand.get_next_long(),
+            rand.get_next_long(),
+        ]
+    }
+
+    #[inline(always)]
+    pub const fn get_base_stone_seed(world_seed: i64, pos: BlockPos) -> i64 {
+        let [a, b, c] = Self::get_base_stone_multipliers(world_seed);
+        (pos.x as i64).wrapping_mul(a)
+            ^ (pos.y as i64).wrapping_mul(b)
+            ^ (pos.z as i64).wrapping_mul(c)
+            ^ world_seed
+    }
+
+    /// Base stone seeds of the column above `bottom`, for `y = bottom.y..bottom.y + seeds.len()`.
+    pub fn fill_base_stone_seed_column(world_seed: i64, bottom: BlockPos, seeds: &mut [i64]) {
+        let [a, b, c] = Self::get_base_stone_multipliers(world_seed);
+        let column =
+            (bottom.x as i64).wrapping_mul(a) ^ (bottom.z as i64).wrapping_mul(c) ^ world_seed;
+        for (y, seed) in (bottom.y..).zip(seeds.iter_mut()) {
+            *seed = column ^ (y as i64).wrapping_mul(b);
+        }
     }
 }
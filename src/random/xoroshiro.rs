@@ -0,0 +1,254 @@
+use crate::pos::BlockPos;
+use crate::random::seeds::PositionSeed;
+
+const GOLDEN_RATIO_64: i64 = -7046029254386353131;
+const SILVER_RATIO_64: i64 = 7640891576956012809;
+
+/// `RandomSupport.mixStafford13`.
+#[inline(always)]
+pub const fn mix_stafford_13(mut value: i64) -> i64 {
+    value = (value ^ (value as u64 >> 30) as i64).wrapping_mul(-4658895280553007687);
+    value = (value ^ (value as u64 >> 27) as i64).wrapping_mul(-7723592293110705685);
+    value ^ (value as u64 >> 31) as i64
+}
+
+/// `RandomSupport.upgradeSeedTo128bit`, returns `(seed_lo, seed_hi)`.
+#[inline(always)]
+pub const fn upgrade_seed_to_128_bit(seed: i64) -> (i64, i64) {
+    let seed_lo = seed ^ SILVER_RATIO_64;
+    let seed_hi = seed_lo.wrapping_add(GOLDEN_RATIO_64);
+    (mix_stafford_13(seed_lo), mix_stafford_13(seed_hi))
+}
+
+/// `XoroshiroRandomSource`, the world generation random from 1.18 on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Xoroshiro {
+    seed_lo: i64,
+    seed_hi: i64,
+}
+
+impl Xoroshiro {
+    const FLOAT_UNIT: f32 = 1.0 / (1 << 24) as f32;
+    const DOUBLE_UNIT: f64 = 1.0 / (1_i64 << 53) as f64;
+
+    #[inline(always)]
+    pub const fn new(seed: i64) -> Self {
+        let (seed_lo, seed_hi) = upgrade_seed_to_128_bit(seed);
+        Self::from_seed_128(seed_lo, seed_hi)
+    }
+
+    #[inline(always)]
+    pub const fn from_seed_128(seed_lo: i64, seed_hi: i64) -> Self {
+        if seed_lo | seed_hi == 0 {
+            Self {
+                seed_lo: GOLDEN_RATIO_64,
+                seed_hi: SILVER_RATIO_64,
+            }
+        } else {
+            Self { seed_lo, seed_hi }
+        }
+    }
+
+    #[inline(always)]
+    pub const fn set_seed(&mut self, seed: i64) {
+        *self = Self::new(seed);
+    }
+
+    #[inline(always)]
+    pub const fn get_next_long(&mut self) -> i64 {
+        let lo = self.seed_lo;
+        let mut hi = self.seed_hi;
+        let value = lo.wrapping_add(hi).rotate_left(17).wrapping_add(lo);
+        hi ^= lo;
+        self.seed_lo = lo.rotate_left(49) ^ hi ^ (hi << 21);
+        self.seed_hi = hi.rotate_left(28);
+        value
+    }
+
+    #[inline(always)]
+    pub const fn next_bits(&mut self, bits: i32) -> i64 {
+        (self.get_next_long() as u64 >> (64 - bits)) as i64
+    }
+
+    #[inline(always)]
+    pub const fn get_next_int(&mut self) -> i32 {
+        self.get_next_long() as i32
+    }
+
+    /// Lemire's nearly divisionless bounded int, as `XoroshiroRandomSource.nextInt(int)`.
+    #[inline(always)]
+    pub const fn get_next_int_bound(&mut self, bound: i32) -> i32 {
+        debug_assert!(bound > 0);
+
+        let mut product = self.get_next_int() as u32 as u64 * bound as u64;
+        let mut low = product & 0xFFFFFFFF;
+        if low < bound as u64 {
+            let threshold = (bound.wrapping_neg() as u32 % bound as u32) as u64;
+            while low < threshold {
+                product = self.get_next_int() as u32 as u64 * bound as u64;
+                low = product & 0xFFFFFFFF;
+            }
+        }
+        (product >> 32) as i32
+    }
+
+    #[inline(always)]
+    pub const fn get_next_bool(&mut self) -> bool {
+        self.get_next_long() & 1 != 0
+    }
+
+    #[inline(always)]
+    pub const fn get_next_float(&mut self) -> f32 {
+        self.next_bits(24) as f32 * Self::FLOAT_UNIT
+    }
+
+    #[inline(always)]
+    pub const fn get_next_double(&mut self) -> f64 {
+        self.next_bits(53) as f64 * Self::DOUBLE_UNIT
+    }
+
+    pub const fn consume_count(&mut self, count: i32) {
+        let mut i = 0;
+        while i < count {
+            self.get_next_long();
+            i += 1;
+        }
+    }
+
+    #[inline(always)]
+    pub fn shuffle<T>(&mut self, vec: &mut [T]) {
+        let size = vec.len();
+        for i in (2..=size).rev() {
+            vec.swap(i - 1, self.get_next_int_bound(i as i32) as usize);
+        }
+    }
+
+    pub const fn fork(&mut self) -> Self {
+        Self::from_seed_128(self.get_next_long(), self.get_next_long())
+    }
+
+    pub const fn fork_positional(&mut self) -> XoroshiroPositional {
+        XoroshiroPositional {
+            seed_lo: self.get_next_long(),
+            seed_hi: self.get_next_long(),
+        }
+    }
+}
+
+/// `XoroshiroPositionalRandomFactory`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct XoroshiroPositional {
+    seed_lo: i64,
+    seed_hi: i64,
+}
+
+impl XoroshiroPositional {
+    pub const fn new(seed_lo: i64, seed_hi: i64) -> Self {
+        Self { seed_lo, seed_hi }
+    }
+
+    #[inline(always)]
+    pub const fn at(&self, pos: BlockPos) -> Xoroshiro {
+        Xoroshiro::from_seed_128(
+            PositionSeed::get_position_seed(pos) ^ self.seed_lo,
+            self.seed_hi,
+        )
+    }
+
+    pub const fn from_seed(&self, seed: i64) -> Xoroshiro {
+        Xoroshiro::from_seed_128(seed ^ self.seed_lo, self.seed_hi)
+    }
+
+    /// `RandomSupport.seedFromHashOf`: the MD5 of the name split into two big endian longs.
+    pub const fn from_hash_of(&self, name: &str) -> Xoroshiro {
+        let digest = md5(name.as_bytes());
+        let mut hash_lo = 0_i64;
+        let mut hash_hi = 0_i64;
+        let mut i = 0;
+        while i < 8 {
+            hash_lo = (hash_lo << 8) | digest[i] as i64;
+            hash_hi = (hash_hi << 8) | digest[i + 8] as i64;
+            i += 1;
+        }
+        Xoroshiro::from_seed_128(hash_lo ^ self.seed_lo, hash_hi ^ self.seed_hi)
+    }
+}
+
+const MD5_S: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+const MD5_K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// MD5 of a short message; names never get close to a second block boundary in practice,
+/// but longer inputs are still padded and processed block by block.
+const fn md5(message: &[u8]) -> [u8; 16] {
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    let padded_len = (message.len() + 8) / 64 * 64 + 64;
+    let bit_len = (message.len() as u64).wrapping_mul(8).to_le_bytes();
+
+    let mut block_start = 0;
+    while block_start < padded_len {
+        let mut words = [0_u32; 16];
+        let mut i = 0;
+        while i < 64 {
+            let index = block_start + i;
+            let byte = if index < message.len() {
+                message[index]
+            } else if index == message.len() {
+                0x80
+            } else if index >= padded_len - 8 {
+                bit_len[index - (padded_len - 8)]
+            } else {
+                0
+            };
+            words[i / 4] |= (byte as u32) << (8 * (i % 4));
+            i += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d] = state;
+        let mut i = 0;
+        while i < 64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let rotated = a
+                .wrapping_add(f)
+                .wrapping_add(MD5_K[i])
+                .wrapping_add(words[g])
+                .rotate_left(MD5_S[i]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(rotated);
+            i += 1;
+        }
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        block_start += 64;
+    }
+
+    let mut digest = [0_u8; 16];
+    let mut i = 0;
+    while i < 16 {
+        digest[i] = state[i / 4].to_le_bytes()[i % 4];
+        i += 1;
+    }
+    digest
+}
@@ -0,0 +1,210 @@
+use std::ops::Range;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::observation::{ChestObservation, Position};
+use crate::pos::{BlockPos, ChunkPos};
+use crate::random::mcversion::MCVersion;
+use crate::structure::Structure;
+use crate::{InventoryVec, ItemStack, DEFAULT_VERSION};
+
+/// Bumped whenever a field changes meaning or goes away; new fields may be added freely.
+pub const SCHEMA: &str = "seedcracker.crack/2";
+
+/// A JSON string literal.
+pub(crate) fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// A chest a crack was run against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    pub structure: Structure,
+    pub chunk: ChunkPos,
+    /// The chest's block, when it was given.
+    pub block: Option<BlockPos>,
+    pub version: MCVersion,
+    pub loot_table: &'static str,
+    /// `None` for a slot that was not seen, an empty stack for one seen to be empty.
+    pub slots: [Option<ItemStack>; 27],
+}
+
+impl Observation {
+    /// A chest of the structure's first loot table that was seen whole.
+    pub fn from_inventory(
+        structure: Structure,
+        chunk: ChunkPos,
+        inventory: &InventoryVec<ItemStack>,
+    ) -> Self {
+        Self {
+            structure,
+            chunk,
+            block: None,
+            version: DEFAULT_VERSION,
+            loot_table: structure.loot_tables()[0].name,
+            slots: std::array::from_fn(|slot| Some(inventory[slot])),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let items: Vec<String> = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, stack)| Some((slot, (*stack)?)))
+            .filter(|(_, stack)| !stack.is_empty())
+            .map(|(slot, stack)| {
+                format!(
+                    "{{\"slot\":{},\"item\":{},\"count\":{}}}",
+                    slot,
+                    json_string(stack.get_item().id()),
+                    stack.get_count()
+                )
+            })
+            .collect();
+        let empty: Vec<String> = self
+            .slots
+            .iter()
+            .enumerate()
+            .filter(|(_, stack)| stack.is_some_and(|stack| stack.is_empty()))
+            .map(|(slot, _)| slot.to_string())
+            .collect();
+        let block = self.block.map_or_else(
+            || "null".to_string(),
+            |block| format!("{{\"x\":{},\"y\":{},\"z\":{}}}", block.x, block.y, block.z),
+        );
+        format!(
+            "{{\"structure\":{},\"chunk\":{{\"x\":{},\"z\":{}}},\"block\":{},\"version\":{},\
+             \"loot_table\":{},\"items\":[{}],\"empty\":[{}]}}",
+            json_string(self.structure.id()),
+            self.chunk.x,
+            self.chunk.z,
+            block,
+            json_string(&self.version.to_string()),
+            json_string(self.loot_table),
+            items.join(","),
+            empty.join(",")
+        )
+    }
+}
+
+impl From<&ChestObservation> for Observation {
+    fn from(observation: &ChestObservation) -> Self {
+        Self {
+            structure: observation.structure,
+            chunk: observation.chunk(),
+            block: match observation.position {
+                Position::Chunk(_) => None,
+                Position::Block(block) => Some(block),
+            },
+            version: observation.version,
+            loot_table: observation.loot_table.name,
+            slots: observation.target.slots,
+        }
+    }
+}
+
+/// A matching structure seed and the world seeds it could be expanded to. World seeds are
+/// written as strings, a JSON number loses the low bits of most of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedResult {
+    pub structure_seed: u64,
+    pub world_seeds: Vec<i64>,
+}
+
+impl SeedResult {
+    pub fn to_json(&self) -> String {
+        let world_seeds: Vec<String> = self
+            .world_seeds
+            .iter()
+            .map(|seed| json_string(&seed.to_string()))
+            .collect();
+        format!(
+            "{{\"structure_seed\":{},\"world_seeds\":[{}]}}",
+            self.structure_seed,
+            world_seeds.join(",")
+        )
+    }
+
+    /// A `hit` record for JSON lines output, written as soon as the seed is found.
+    pub fn to_json_line(&self) -> String {
+        format!(
+            "{{\"schema\":{},\"type\":\"hit\",{}",
+            json_string(SCHEMA),
+            &self.to_json()[1..]
+        )
+    }
+}
+
+/// Everything about one crack run, enough to archive and post-process it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrackReport {
+    pub observations: Vec<Observation>,
+    pub backend: &'static str,
+    pub range: Range<u64>,
+    pub scanned: u64,
+    pub started: SystemTime,
+    pub elapsed: Duration,
+    pub results: Vec<SeedResult>,
+}
+
+impl CrackReport {
+    fn fields(&self) -> String {
+        let started = self
+            .started
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let elapsed = self.elapsed.as_secs_f64();
+        let rate = if elapsed == 0.0 {
+            0.0
+        } else {
+            self.scanned as f64 / elapsed
+        };
+        let observations: Vec<String> =
+            self.observations.iter().map(Observation::to_json).collect();
+        let results: Vec<String> = self.results.iter().map(SeedResult::to_json).collect();
+        format!(
+            "\"observations\":[{}],\"backend\":{},\
+             \"range\":{{\"start\":{},\"end\":{},\"scanned\":{}}},\
+             \"timings\":{{\"started_unix_ms\":{},\"elapsed_secs\":{:.3},\"seeds_per_sec\":{:.1}}},\
+             \"results\":[{}]",
+            observations.join(","),
+            json_string(self.backend),
+            self.range.start,
+            self.range.end,
+            self.scanned,
+            started,
+            elapsed,
+            rate,
+            results.join(",")
+        )
+    }
+
+    /// The whole report as one JSON document.
+    pub fn to_json(&self) -> String {
+        format!("{{\"schema\":{},{}}}", json_string(SCHEMA), self.fields())
+    }
+
+    /// The closing `summary` record for JSON lines output.
+    pub fn to_json_line(&self) -> String {
+        format!(
+            "{{\"schema\":{},\"type\":\"summary\",{}}}",
+            json_string(SCHEMA),
+            self.fields()
+        )
+    }
+}
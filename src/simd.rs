@@ -0,0 +1,169 @@
+use std::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+use std::simd::num::{SimdInt, SimdUint};
+use std::simd::{Mask, Select, Simd};
+
+use crate::item::Item;
+use crate::observation::LootTarget;
+use crate::pos::ChunkPos;
+use crate::random::mth::MASK_48;
+use crate::structure::Structure;
+use crate::{check_seed, BURIED_TREASURE_SALT};
+
+/// Seeds checked together by `check_seeds` in the scanners, 8 fills an AVX-512 register.
+pub const LANES: usize = 8;
+
+const MULTIPLIER: i64 = 0x5deece66d;
+const ADDEND: i64 = 0xb;
+
+/// `java.util.Random` stepped in lockstep for every lane. Lanes that are not active keep
+/// their seed, so each one can make a different number of calls.
+struct SimdRand<const N: usize> {
+    seed: Simd<i64, N>,
+}
+
+impl<const N: usize> SimdRand<N> {
+    #[inline(always)]
+    fn new(seed: Simd<i64, N>) -> Self {
+        Self {
+            seed: (seed ^ Simd::splat(MULTIPLIER)) & Simd::splat(MASK_48),
+        }
+    }
+
+    #[inline(always)]
+    fn next(&mut self, bits: i64, active: Mask<i64, N>) -> Simd<i64, N> {
+        let next =
+            (self.seed * Simd::splat(MULTIPLIER) + Simd::splat(ADDEND)) & Simd::splat(MASK_48);
+        self.seed = active.select(next, self.seed);
+        // Java keeps the result in an int, so `next(32)` comes out signed.
+        (self.seed >> Simd::splat(48 - bits))
+            .cast::<i32>()
+            .cast::<i64>()
+    }
+
+    #[inline(always)]
+    fn next_long(&mut self, active: Mask<i64, N>) -> Simd<i64, N> {
+        let high = self.next(32, active);
+        (high << Simd::splat(32)) + self.next(32, active)
+    }
+
+    /// `nextInt(1 << bits)`, which never rejects a draw.
+    #[inline(always)]
+    fn next_int_pow2(&mut self, bits: i64, active: Mask<i64, N>) -> Simd<i64, N> {
+        self.next(31, active) >> Simd::splat(31 - bits)
+    }
+
+    /// `nextInt(bound)` for a bound that is not a power of two. A rejected draw would need
+    /// another call, so those lanes are reported instead of retried.
+    #[inline(always)]
+    fn next_int(&mut self, bound: i64, active: Mask<i64, N>) -> (Simd<i64, N>, Mask<i64, N>) {
+        let bits = self.next(31, active);
+        let value = bits % Simd::splat(bound);
+        let rejected =
+            (bits - value + Simd::splat(bound - 1)).simd_gt(Simd::splat(i32::MAX as i64));
+        (value, rejected & active)
+    }
+}
+
+/// `can_spawn_buried_treasure` for every lane.
+#[inline(always)]
+fn can_spawn_buried_treasure_lanes<const N: usize>(
+    seeds: Simd<i64, N>,
+    chunk: ChunkPos,
+) -> Mask<i64, N> {
+    let region_seed = Simd::splat(
+        chunk.x as i64 * 341873128712 + chunk.z as i64 * 132897987541 + BURIED_TREASURE_SALT,
+    );
+    let mut rand = SimdRand::new(seeds + region_seed);
+    // `nextFloat() < 0.01` on 24 bits, exact since every draw fits in a float.
+    rand.next(24, Mask::splat(true))
+        .simd_lt(Simd::splat(167773))
+}
+
+/// Everything `check_seed` needs before the first loot pool, then that pool's iron, gold and
+/// TNT totals against the target: equal when every slot is known, at least the known ones
+/// otherwise. Lanes left set may match, the rest cannot.
+#[inline(always)]
+fn first_pool_matches<const N: usize>(
+    seeds: Simd<i64, N>,
+    chunk: ChunkPos,
+    target: &LootTarget,
+) -> Mask<i64, N> {
+    let all = Mask::splat(true);
+    let block = chunk.get_min_block();
+
+    let mut rand = SimdRand::new(seeds);
+    let a = rand.next_long(all) | Simd::splat(1);
+    let b = rand.next_long(all) | Simd::splat(1);
+    let population_seed =
+        (Simd::splat(block.x as i64) * a + Simd::splat(block.z as i64) * b) ^ seeds;
+    let mut rand =
+        SimdRand::new(population_seed + Simd::splat(Structure::BuriedTreasure.salt() as i64));
+    let mut rand = SimdRand::new(rand.next_long(all));
+
+    let rolls = rand.next_int_pow2(2, all) + Simd::splat(5);
+    let mut iron = Simd::splat(0);
+    let mut gold = Simd::splat(0);
+    let mut tnt = Simd::splat(0);
+    let mut rejected = Mask::splat(false);
+    for roll in 0..8 {
+        let active = Simd::splat(roll).simd_lt(rolls) & !rejected;
+        let (weight, retry) = rand.next_int(35, active);
+        rejected |= retry;
+        let active = active & !retry;
+
+        let is_iron = weight.simd_lt(Simd::splat(20));
+        let is_gold = !is_iron & weight.simd_lt(Simd::splat(30));
+        let is_tnt = !is_iron & !is_gold;
+        // Ingots roll `nextInt(4)` and TNT `nextInt(2)`, both the top bits of one draw.
+        let count = rand.next(31, active);
+        let ingots = (count >> Simd::splat(29)) + Simd::splat(1);
+        iron += (active & is_iron).select(ingots, Simd::splat(0));
+        gold += (active & is_gold).select(ingots, Simd::splat(0));
+        tnt +=
+            (active & is_tnt).select((count >> Simd::splat(30)) + Simd::splat(1), Simd::splat(0));
+    }
+
+    let agrees = |total: Simd<i64, N>, item| {
+        let (count, exact) = target.count(item);
+        let count = Simd::splat(count as i64);
+        if exact {
+            total.simd_eq(count)
+        } else {
+            total.simd_ge(count)
+        }
+    };
+    rejected
+        | (agrees(iron, Item::IronIngot) & agrees(gold, Item::GoldIngot) & agrees(tnt, Item::TNT))
+}
+
+/// `can_spawn_buried_treasure` for `N` seeds at once, bit `i` set when `seeds[i]` passes.
+#[must_use]
+pub fn can_spawn_buried_treasure_seeds<const N: usize>(seeds: &[u64; N], chunk: ChunkPos) -> u64 {
+    let lanes = Simd::<u64, N>::from_array(*seeds).cast::<i64>();
+    can_spawn_buried_treasure_lanes(lanes, chunk).to_bitmask()
+}
+
+/// `check_seed` for `N` seeds at once, bit `i` of the result set when `seeds[i]` matches.
+///
+/// The spawn check and the first loot pool run in lockstep; only the lanes that get through
+/// both are finished with the scalar `check_seed`.
+#[must_use]
+pub fn check_seeds<const N: usize>(seeds: &[u64; N], chunk: ChunkPos, target: &LootTarget) -> u64 {
+    let lanes = Simd::<u64, N>::from_array(*seeds).cast::<i64>();
+    let mut survivors = can_spawn_buried_treasure_lanes(lanes, chunk);
+    if !survivors.any() {
+        return 0;
+    }
+    survivors &= first_pool_matches(lanes, chunk, target);
+
+    let mut mask = survivors.to_bitmask();
+    let mut result = 0;
+    while mask != 0 {
+        let lane = mask.trailing_zeros() as usize;
+        if check_seed(seeds[lane], chunk, target) {
+            result |= 1 << lane;
+        }
+        mask &= mask - 1;
+    }
+    result
+}
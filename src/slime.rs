@@ -0,0 +1,120 @@
+use std::ops::Range;
+
+use crate::pos::ChunkPos;
+use crate::random::chunkrand::ChunkRand;
+use crate::random::mth::MASK_48;
+
+const SLIME_SCRAMBLER: i64 = 987234911;
+
+/// Number of low seed bits that decide the parity of `nextInt(10)`.
+pub const LOW_BITS: i32 = 18;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SlimeChunk {
+    pub chunk: ChunkPos,
+    pub is_slime: bool,
+}
+
+impl SlimeChunk {
+    pub const fn new(chunk: ChunkPos, is_slime: bool) -> Self {
+        Self { chunk, is_slime }
+    }
+
+    /// What the chunk adds to the world seed, the slime seed of world seed zero unscrambled.
+    #[inline(always)]
+    const fn offset(&self) -> i64 {
+        ChunkRand::new_scramble(0, false).set_slime_seed_scramble(0, self.chunk, 0)
+    }
+
+    /// `nextInt(10) == 0` needs an even `next(31)`, i.e. bit 17 of the first state clear.
+    /// That bit only depends on the low 18 bits of the seed.
+    #[inline(always)]
+    const fn matches_low_bits(&self, low_bits: i64) -> bool {
+        if !self.is_slime {
+            return true;
+        }
+        let seed = ((low_bits + self.offset()) ^ SLIME_SCRAMBLER ^ 0x5deece66d) & MASK_48;
+        (seed.wrapping_mul(0x5deece66d).wrapping_add(0xb) >> 17) & 1 == 0
+    }
+
+    #[inline(always)]
+    pub fn matches(&self, world_seed: i64) -> bool {
+        is_slime_chunk(world_seed, self.chunk) == self.is_slime
+    }
+}
+
+#[inline(always)]
+pub fn is_slime_chunk(world_seed: i64, chunk: ChunkPos) -> bool {
+    let mut rand = ChunkRand::default();
+    rand.set_slime_seed(world_seed, chunk);
+    rand.get_next_int_bound(10) == 0
+}
+
+/// Cracks the lower 48 bits of a world seed from known slime and non-slime chunks.
+/// The upper 16 bits are never used by the slime check, so every returned structure
+/// seed stands for 2^16 world seeds.
+pub struct SlimeCracker {
+    observations: Vec<SlimeChunk>,
+}
+
+impl SlimeCracker {
+    pub fn new(observations: &[SlimeChunk]) -> Self {
+        let mut observations = observations.to_vec();
+        // Slime chunks are rarer, so they reject a wrong seed sooner.
+        observations.sort_by_key(|observation| !observation.is_slime);
+        Self { observations }
+    }
+
+    pub fn observations(&self) -> &[SlimeChunk] {
+        &self.observations
+    }
+
+    pub fn low_bits_candidates(&self) -> Vec<i64> {
+        (0..1_i64 << LOW_BITS)
+            .filter(|&low_bits| {
+                self.observations
+                    .iter()
+                    .all(|observation| observation.matches_low_bits(low_bits))
+            })
+            .collect()
+    }
+
+    #[inline(always)]
+    pub fn check(&self, structure_seed: u64) -> bool {
+        self.observations
+            .iter()
+            .all(|observation| observation.matches(structure_seed as i64))
+    }
+
+    /// Lifts one low-bits candidate to all matching 48-bit structure seeds.
+    pub fn lift(&self, low_bits: i64) -> Vec<u64> {
+        self.lift_in(low_bits, 0..MASK_48 as u64 + 1)
+    }
+
+    /// Lifts one low-bits candidate to the matching structure seeds within `range`.
+    pub fn lift_in(&self, low_bits: i64, range: Range<u64>) -> Vec<u64> {
+        let step = 1_u64 << LOW_BITS;
+        let mask = step - 1;
+        // The first seed of the range with these low bits.
+        let mut first = range.start & !mask | low_bits as u64 & mask;
+        if first < range.start {
+            first += step;
+        }
+        (first..range.end)
+            .step_by(step as usize)
+            .filter(|&structure_seed| self.check(structure_seed))
+            .collect()
+    }
+
+    pub fn crack(&self) -> Vec<u64> {
+        self.crack_in(0..MASK_48 as u64 + 1)
+    }
+
+    /// Cracks only the structure seeds within `range`.
+    pub fn crack_in(&self, range: Range<u64>) -> Vec<u64> {
+        self.low_bits_candidates()
+            .into_iter()
+            .flat_map(|low_bits| self.lift_in(low_bits, range.clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,203 @@
+use crate::loot::tables::{
+    BASTION_TREASURE, BURIED_TREASURE, END_CITY_TREASURE, VILLAGE_CHESTS, WOODLAND_MANSION,
+};
+use crate::loot::LootTable;
+use crate::pos::ChunkPos;
+use crate::random::chunkrand::ChunkRand;
+use crate::random::jrand::LCG;
+use crate::random::mcversion::{MCVersion, V1_13, V1_18};
+use crate::random::mth::MASK_48;
+use crate::random::reverse::next_long_states;
+use crate::random::seeds::PopulationSeed;
+use crate::{InventoryVec, ItemStack, DEFAULT_VERSION};
+
+const UNDERGROUND_STRUCTURES: i32 = 3;
+const SURFACE_STRUCTURES: i32 = 4;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Structure {
+    BuriedTreasure,
+    WoodlandMansion,
+    EndCity,
+    Village,
+    BastionRemnant,
+}
+
+impl Structure {
+    pub const ALL: [Self; 5] = [
+        Self::BuriedTreasure,
+        Self::WoodlandMansion,
+        Self::EndCity,
+        Self::Village,
+        Self::BastionRemnant,
+    ];
+
+    pub const fn id(&self) -> &'static str {
+        match self {
+            Self::BuriedTreasure => "minecraft:buried_treasure",
+            Self::WoodlandMansion => "minecraft:mansion",
+            Self::EndCity => "minecraft:end_city",
+            Self::Village => "minecraft:village",
+            Self::BastionRemnant => "minecraft:bastion_remnant",
+        }
+    }
+
+    /// The structure with resource location `id`, the `minecraft:` namespace may be left out.
+    pub fn from_id(id: &str) -> Option<Self> {
+        let id = id.strip_prefix("minecraft:").unwrap_or(id);
+        Self::ALL
+            .into_iter()
+            .find(|structure| structure.id().strip_prefix("minecraft:") == Some(id))
+    }
+
+    /// Index of the structure within its generation step, in 1.16.2+ registry order.
+    pub const fn index(&self) -> i32 {
+        match self {
+            Self::BuriedTreasure => 1,
+            Self::WoodlandMansion => 1,
+            Self::EndCity => 10,
+            Self::Village => 11,
+            Self::BastionRemnant => 12,
+        }
+    }
+
+    pub const fn step(&self) -> i32 {
+        match self {
+            Self::BuriedTreasure => UNDERGROUND_STRUCTURES,
+            Self::WoodlandMansion | Self::EndCity | Self::Village | Self::BastionRemnant => {
+                SURFACE_STRUCTURES
+            }
+        }
+    }
+
+    /// The decorator salt the chunk's piece generation random is seeded with.
+    pub const fn salt(&self) -> i32 {
+        self.index() + 10000 * self.step()
+    }
+
+    pub const fn loot_tables(&self) -> &'static [LootTable] {
+        match self {
+            Self::BuriedTreasure => &[BURIED_TREASURE],
+            Self::WoodlandMansion => &[WOODLAND_MANSION],
+            Self::EndCity => &[END_CITY_TREASURE],
+            Self::Village => &VILLAGE_CHESTS,
+            Self::BastionRemnant => &[BASTION_TREASURE],
+        }
+    }
+}
+
+/// Seeds the random the structure pieces in a chunk are placed with. From 1.18 on this is
+/// a `Xoroshiro` source, while the loot tables themselves keep rolling with a
+/// `java.util.Random` seeded from it.
+#[inline(always)]
+pub fn get_piece_rand(
+    structure: Structure,
+    structure_seed: i64,
+    chunk: ChunkPos,
+    version: &MCVersion,
+) -> ChunkRand {
+    let mut rand = ChunkRand::default().with_version(*version);
+    rand.set_decorator_seed_block_salt(structure_seed, chunk.get_min_block(), structure.salt());
+    rand
+}
+
+/// Pieces share one random per chunk and every container they place takes one `nextLong`,
+/// so the loot seed of a chest depends on how many draws came before it in the chunk.
+///
+/// `chest_index` is the number of `nextLong`s taken before this chest's, which is not
+/// always the number of earlier containers: village feature pool elements such as trees,
+/// hay bales and decorations draw from the same random, and have to be counted as well.
+#[inline(always)]
+pub fn get_loot_seed(
+    structure: Structure,
+    structure_seed: i64,
+    chunk: ChunkPos,
+    chest_index: u32,
+    version: &MCVersion,
+) -> i64 {
+    let mut rand = get_piece_rand(structure, structure_seed, chunk, version);
+    for _ in 0..chest_index {
+        rand.get_next_long();
+    }
+    rand.get_next_long()
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StructureChest {
+    pub structure: Structure,
+    pub loot_table: &'static LootTable,
+    pub chunk: ChunkPos,
+    /// How many `nextLong`s the chunk random gave out before this chest's, see
+    /// `get_loot_seed`.
+    pub chest_index: u32,
+    pub version: MCVersion,
+}
+
+impl StructureChest {
+    pub const fn new(structure: Structure, chunk: ChunkPos, chest_index: u32) -> Self {
+        Self {
+            structure,
+            loot_table: &structure.loot_tables()[0],
+            chunk,
+            chest_index,
+            version: DEFAULT_VERSION,
+        }
+    }
+
+    pub const fn with_loot_table(self, loot_table: &'static LootTable) -> Self {
+        Self { loot_table, ..self }
+    }
+
+    /// Seeds the pieces the way `version` does.
+    pub const fn with_version(self, version: MCVersion) -> Self {
+        Self { version, ..self }
+    }
+
+    pub fn get_loot_seed(&self, structure_seed: i64) -> i64 {
+        get_loot_seed(
+            self.structure,
+            structure_seed,
+            self.chunk,
+            self.chest_index,
+            &self.version,
+        )
+    }
+
+    /// The structure seeds whose chunk random hands this chest `loot_seed`, which an
+    /// unopened chest keeps as its `LootTableSeed`. The `nextLong` gives back the piece
+    /// random's state, stepping back over the earlier draws and taking the salt off leaves
+    /// the population seed, and that is reversed in turn.
+    ///
+    /// Only 1.13 to 1.17 are supported: older releases seed population by chunk, and 1.18
+    /// seeds the pieces with `Xoroshiro`.
+    pub fn reverse_loot_seed(&self, loot_seed: i64) -> Result<Vec<i64>, String> {
+        if self.version.is_older_than(&V1_13) || !self.version.is_older_than(&V1_18) {
+            return Err(format!(
+                "loot seeds can only be reversed for chests generated from 1.13 to 1.17, not {}",
+                self.version
+            ));
+        }
+        let back = LCG::JAVA.combine_steps(-2 * self.chest_index as i64);
+        let block = self.chunk.get_min_block();
+        let mut seeds: Vec<i64> = next_long_states(loot_seed)
+            .into_iter()
+            .map(|state| (back.next_seed(state) ^ 0x5deece66d) & MASK_48)
+            .flat_map(|decorator_seed| {
+                PopulationSeed::reverse(decorator_seed - self.structure.salt() as i64, block)
+            })
+            .filter(|&seed| self.get_loot_seed(seed) == loot_seed)
+            .collect();
+        seeds.sort_unstable();
+        seeds.dedup();
+        Ok(seeds)
+    }
+
+    pub fn get_loot(&self, structure_seed: i64, indexed: bool) -> InventoryVec<ItemStack> {
+        self.loot_table
+            .get_loot(self.get_loot_seed(structure_seed), indexed)
+    }
+
+    pub fn check(&self, structure_seed: i64, target: &InventoryVec<ItemStack>) -> bool {
+        self.get_loot(structure_seed, true) == *target
+    }
+}
@@ -0,0 +1,66 @@
+use std::ops::RangeInclusive;
+
+use crate::random::mth::MASK_48;
+
+/// `String.hashCode`, over UTF-16 code units like Java.
+pub fn java_hash_code(text: &str) -> i32 {
+    text.encode_utf16().fold(0_i32, |hash, unit| {
+        hash.wrapping_mul(31).wrapping_add(unit as i32)
+    })
+}
+
+/// The world seed the create world screen picks for `text`, as `WorldOptions.parseSeed`.
+///
+/// The text is trimmed like `String.trim`, which only drops control characters and spaces,
+/// numbers are taken as they are and anything else goes through `String.hashCode`. A
+/// blank field means a random seed.
+pub fn text_to_world_seed(text: &str) -> Option<i64> {
+    let text = text.trim_matches(|c| c <= ' ');
+    if text.is_empty() {
+        return None;
+    }
+    Some(
+        text.parse::<i64>()
+            .unwrap_or_else(|_| java_hash_code(text) as i64),
+    )
+}
+
+/// Whether a world seed could have come from a non numeric text, that is whether it is a
+/// sign extended 32 bit value.
+#[inline(always)]
+pub const fn is_hash_reachable(world_seed: i64) -> bool {
+    world_seed as i32 as i64 == world_seed
+}
+
+/// The only hash reachable world seed with these lower 48 bits, if there is one.
+#[inline(always)]
+pub const fn hash_world_seed_from_structure_seed(structure_seed: u64) -> Option<i64> {
+    let world_seed = structure_seed as i32 as i64;
+    if world_seed as u64 & MASK_48 as u64 == structure_seed {
+        Some(world_seed)
+    } else {
+        None
+    }
+}
+
+/// All 2^32 world seeds a text seed can produce.
+pub fn hash_reachable_world_seeds() -> impl Iterator<Item = i64> {
+    (i32::MIN..=i32::MAX).map(i64::from)
+}
+
+/// Runs a structure seed check over the hash reachable seeds only, returning the world
+/// seeds that pass.
+pub fn find_text_world_seeds(check: impl Fn(u64) -> bool) -> Vec<i64> {
+    find_text_world_seeds_in(i32::MIN..=i32::MAX, check)
+}
+
+/// `find_text_world_seeds` over the text hashes within `hashes`.
+pub fn find_text_world_seeds_in(
+    hashes: RangeInclusive<i32>,
+    check: impl Fn(u64) -> bool,
+) -> Vec<i64> {
+    hashes
+        .map(i64::from)
+        .filter(|&world_seed| check(world_seed as u64 & MASK_48 as u64))
+        .collect()
+}
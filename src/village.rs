@@ -0,0 +1,74 @@
+use crate::loot::LootTable;
+use crate::observation::LootTarget;
+use crate::pos::{ChunkPos, RegionPos};
+use crate::random::chunkrand::ChunkRand;
+use crate::structure::{Structure, StructureChest};
+
+pub const VILLAGE_SPACING: i32 = 32;
+pub const VILLAGE_SEPARATION: i32 = 8;
+pub const VILLAGE_SALT: i32 = 10387312;
+
+/// The chunk a village may start in within a region, before any biome check.
+#[inline(always)]
+pub fn get_village_chunk(structure_seed: i64, region: RegionPos) -> ChunkPos {
+    let mut rand = ChunkRand::default();
+    rand.set_region_seed(structure_seed, region, VILLAGE_SALT);
+    let offset_x = rand.get_next_int_bound(VILLAGE_SPACING - VILLAGE_SEPARATION);
+    let offset_z = rand.get_next_int_bound(VILLAGE_SPACING - VILLAGE_SEPARATION);
+    ChunkPos::new(
+        region.x * VILLAGE_SPACING + offset_x,
+        region.z * VILLAGE_SPACING + offset_z,
+    )
+}
+
+#[inline(always)]
+pub fn can_spawn_village(structure_seed: i64, chunk: ChunkPos) -> bool {
+    get_village_chunk(structure_seed, chunk.to_region(VILLAGE_SPACING)) == chunk
+}
+
+/// One observed village chest. Houses are usually in a different chunk than the village
+/// start, so the start chunk is optional and only used as a cheap first filter.
+///
+/// There is no scan of its own: `ChestObservation::to_clue` turns a village chest into a
+/// `Clue::Village`, which the joint cracker scans along with any other chests.
+#[derive(Debug, Copy, Clone)]
+pub struct VillageCracker {
+    pub start_chunk: Option<ChunkPos>,
+    pub chest: StructureChest,
+    pub target: LootTarget,
+}
+
+impl VillageCracker {
+    pub const fn new(
+        start_chunk: Option<ChunkPos>,
+        chest_chunk: ChunkPos,
+        chest_index: u32,
+        loot_table: &'static LootTable,
+        target: LootTarget,
+    ) -> Self {
+        Self {
+            start_chunk,
+            chest: StructureChest::new(Structure::Village, chest_chunk, chest_index)
+                .with_loot_table(loot_table),
+            target,
+        }
+    }
+
+    /// Whether a village starts in `start_chunk`, or always when it is not known.
+    #[inline(always)]
+    pub fn can_spawn(&self, structure_seed: u64) -> bool {
+        self.start_chunk
+            .is_none_or(|chunk| can_spawn_village(structure_seed as i64, chunk))
+    }
+
+    #[inline(always)]
+    pub fn matches_loot(&self, structure_seed: u64) -> bool {
+        self.target
+            .matches(&self.chest.get_loot(structure_seed as i64, true))
+    }
+
+    #[inline(always)]
+    pub fn check(&self, structure_seed: u64) -> bool {
+        self.can_spawn(structure_seed) && self.matches_loot(structure_seed)
+    }
+}
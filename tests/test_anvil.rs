@@ -0,0 +1,281 @@
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use seedcracker::anvil::{data_version_release, find_chests, RegionFile};
+use seedcracker::item::Item;
+use seedcracker::loot::tables::{BURIED_TREASURE, VILLAGE_WEAPONSMITH};
+use seedcracker::nbt::{binary, Tag};
+use seedcracker::observation::{ChestObservation, Position};
+use seedcracker::pos::BlockPos;
+use seedcracker::random::mcversion::{V1_12, V1_16, V1_16_5, V1_17, V1_18, V1_8};
+use seedcracker::structure::Structure;
+use seedcracker::ItemStack;
+
+fn compound(entries: Vec<(&str, Tag)>) -> Tag {
+    Tag::Compound(
+        entries
+            .into_iter()
+            .map(|(name, tag)| (name.to_string(), tag))
+            .collect(),
+    )
+}
+
+fn string(value: &str) -> Tag {
+    Tag::String(value.to_string())
+}
+
+fn chest(id: &str, pos: BlockPos, extra: Vec<(&str, Tag)>) -> Tag {
+    let mut entries = vec![
+        ("id", string(id)),
+        ("x", Tag::Int(pos.x)),
+        ("y", Tag::Int(pos.y)),
+        ("z", Tag::Int(pos.z)),
+    ];
+    entries.extend(extra);
+    compound(entries)
+}
+
+/// An opened buried treasure chest, an unopened village barrel, a chest outside any
+/// structure and a furnace.
+fn block_entities() -> Tag {
+    let items = Tag::List(vec![
+        compound(vec![
+            ("Slot", Tag::Byte(3)),
+            ("id", string("minecraft:diamond")),
+            ("Count", Tag::Byte(2)),
+        ]),
+        compound(vec![
+            ("Slot", Tag::Byte(7)),
+            ("id", string("minecraft:heart_of_the_sea")),
+            ("Count", Tag::Byte(1)),
+        ]),
+    ]);
+    Tag::List(vec![
+        chest(
+            "minecraft:chest",
+            BlockPos::new(-439, 61, -1159),
+            vec![("Items", items.clone())],
+        ),
+        chest(
+            "minecraft:barrel",
+            BlockPos::new(-436, 70, -1155),
+            vec![
+                (
+                    "LootTable",
+                    string("minecraft:chests/village/village_weaponsmith"),
+                ),
+                ("LootTableSeed", Tag::Long(1234)),
+            ],
+        ),
+        chest(
+            "minecraft:chest",
+            BlockPos::new(-430, 70, -1150),
+            vec![("Items", items)],
+        ),
+        chest(
+            "minecraft:furnace",
+            BlockPos::new(-431, 70, -1150),
+            Vec::new(),
+        ),
+    ])
+}
+
+/// The start of the buried treasure around the opened chest, under `id`.
+fn treasure_start(id: &str) -> Tag {
+    compound(vec![
+        ("id", string(id)),
+        ("ChunkX", Tag::Int(-28)),
+        ("ChunkZ", Tag::Int(-73)),
+        (
+            "Children",
+            Tag::List(vec![compound(vec![(
+                "BB",
+                Tag::IntArray(vec![-439, 60, -1159, -439, 62, -1159]),
+            )])]),
+        ),
+    ])
+}
+
+/// A chunk saved by 1.16.5, everything but its `DataVersion` inside `Level`.
+fn chunk() -> Tag {
+    compound(vec![
+        ("DataVersion", Tag::Int(2586)),
+        (
+            "Level",
+            compound(vec![
+                ("xPos", Tag::Int(-28)),
+                ("zPos", Tag::Int(-73)),
+                (
+                    "Structures",
+                    compound(vec![(
+                        "Starts",
+                        compound(vec![("buried_treasure", treasure_start("buried_treasure"))]),
+                    )]),
+                ),
+                ("TileEntities", block_entities()),
+            ]),
+        ),
+    ])
+}
+
+/// The same chunk generated by 1.18.2, which dropped `Level` and renamed the lists.
+fn chunk_1_18() -> Tag {
+    compound(vec![
+        ("DataVersion", Tag::Int(2975)),
+        ("xPos", Tag::Int(-28)),
+        ("zPos", Tag::Int(-73)),
+        (
+            "structures",
+            compound(vec![(
+                "starts",
+                compound(vec![(
+                    "minecraft:buried_treasure",
+                    treasure_start("minecraft:buried_treasure"),
+                )]),
+            )]),
+        ),
+        ("block_entities", block_entities()),
+    ])
+}
+
+fn region_bytes(chunks: &[(usize, usize, &Tag)]) -> Vec<u8> {
+    let mut data = vec![0; 8192];
+    for &(x, z, tag) in chunks {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&binary::write("", tag)).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let sector = data.len() / 4096;
+        let sectors = (compressed.len() + 5).div_ceil(4096);
+        let entry = 4 * (x + z * 32);
+        data[entry..entry + 3].copy_from_slice(&(sector as u32).to_be_bytes()[1..]);
+        data[entry + 3] = sectors as u8;
+        data.extend_from_slice(&(compressed.len() as u32 + 1).to_be_bytes());
+        data.push(2);
+        data.extend_from_slice(&compressed);
+        data.resize((sector + sectors) * 4096, 0);
+    }
+    data
+}
+
+#[test]
+fn test_nbt_roundtrip() {
+    let tag = chunk();
+    let bytes = binary::write("root", &tag);
+    assert_eq!(binary::read(&bytes), Ok(("root".to_string(), tag)));
+    assert!(binary::read(&bytes[..bytes.len() - 1]).is_err());
+}
+
+#[test]
+fn test_find_chests() {
+    let chests = find_chests(&chunk(), None).unwrap();
+    assert_eq!(chests.len(), 2);
+
+    let treasure = &chests[0];
+    assert_eq!(treasure.structure, Structure::BuriedTreasure);
+    assert_eq!(treasure.version, V1_16);
+    assert_eq!(treasure.loot_table, &BURIED_TREASURE);
+    assert_eq!(
+        treasure.position,
+        Position::Block(BlockPos::new(-439, 61, -1159))
+    );
+    assert_eq!(
+        treasure.target.slots[3],
+        Some(ItemStack::new(Item::Diamond, 2))
+    );
+    assert!(treasure.target.slots[0].is_some_and(|stack| stack.is_empty()));
+    assert_eq!(treasure.loot_seed, None);
+
+    let village = &chests[1];
+    assert_eq!(village.structure, Structure::Village);
+    assert_eq!(village.loot_table, &VILLAGE_WEAPONSMITH);
+    assert_eq!(village.loot_seed, Some(1234));
+    assert!(village
+        .target
+        .matches(&VILLAGE_WEAPONSMITH.get_loot(1234, true)));
+
+    // What gets printed reads back as the same chest.
+    for chest in &chests {
+        assert_eq!(
+            ChestObservation::parse(&chest.to_string()).as_ref(),
+            Ok(chest)
+        );
+    }
+
+    // The 1.18 layout holds the same chests.
+    let chests_1_18 = find_chests(&chunk_1_18(), None).unwrap();
+    assert_eq!(chests_1_18.len(), 2);
+    for (chest, chest_1_18) in chests.iter().zip(&chests_1_18) {
+        assert_eq!(chest_1_18.version, V1_18);
+        assert_eq!(
+            (chest_1_18.position, chest_1_18.loot_seed, chest_1_18.target),
+            (chest.position, chest.loot_seed, chest.target)
+        );
+    }
+}
+
+#[test]
+fn test_find_chests_upgraded() {
+    // 1.18 keeps `blending_data` on chunks it upgraded from an older world.
+    let mut chunk = chunk_1_18();
+    let Tag::Compound(entries) = &mut chunk else {
+        unreachable!()
+    };
+    entries.push((
+        "blending_data".to_string(),
+        compound(vec![
+            ("min_section", Tag::Int(0)),
+            ("max_section", Tag::Int(16)),
+        ]),
+    ));
+    assert_eq!(
+        find_chests(&chunk, None),
+        Err(
+            "chunk -28 -73 was generated before 1.18 and upgraded by 1.18, the release it was \
+             generated in has to be given"
+                .to_string()
+        )
+    );
+    let chests = find_chests(&chunk, Some(V1_16_5)).unwrap();
+    assert_eq!(chests.len(), 2);
+    assert!(chests.iter().all(|chest| chest.version == V1_16_5));
+
+    let region = RegionFile::from_bytes(region_bytes(&[(4, 23, &chunk)])).unwrap();
+    let (chests, errors) = region.find_chests(None);
+    assert_eq!((chests.len(), errors.len()), (0, 1));
+    assert_eq!(region.find_chests(Some(V1_16_5)).0.len(), 2);
+}
+
+#[test]
+fn test_region_file() {
+    let tag = chunk();
+    let region = RegionFile::from_bytes(region_bytes(&[(4, 23, &tag)])).unwrap();
+    assert_eq!(region.chunk(4, 23), Ok(Some(tag)));
+    assert_eq!(region.chunk(0, 0), Ok(None));
+    let (chests, errors) = region.find_chests(None);
+    assert_eq!((chests.len(), errors.len()), (2, 0));
+
+    // An unreadable chunk is reported and the others are still searched.
+    let mut corrupt = region_bytes(&[(1, 1, &chunk()), (2, 1, &chunk())]);
+    corrupt[8192 + 4] = 9;
+    let corrupt = RegionFile::from_bytes(corrupt).unwrap();
+    assert_eq!(
+        corrupt.chunk(1, 1),
+        Err("chunk 1 1: unknown compression type 9".to_string())
+    );
+    let (chests, errors) = corrupt.find_chests(None);
+    assert_eq!(chests.len(), 2);
+    assert_eq!(errors, ["chunk 1 1: unknown compression type 9"]);
+    assert!(RegionFile::from_bytes(vec![0; 100]).is_err());
+}
+
+#[test]
+fn test_data_version_release() {
+    assert_eq!(data_version_release(Some(2586)), V1_16);
+    assert_eq!(data_version_release(Some(2860)), V1_18);
+    // A 1.18 snapshot seeds like the release before it.
+    assert_eq!(data_version_release(Some(2825)), V1_17);
+    assert_eq!(data_version_release(Some(1343)), V1_12);
+    assert_eq!(data_version_release(None), V1_8);
+}
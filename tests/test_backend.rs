@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use seedcracker::backend::cpu::CpuBackend;
+use seedcracker::backend::progress::{ProgressSnapshot, ProgressTracker};
+use seedcracker::backend::scheduler::WorkQueue;
+use seedcracker::backend::{Backend, CollectHits, ScanReporter, ScanTarget, TreasureTarget};
+
+const SEED: u64 = -4872636734044769429_i64 as u64;
+
+#[test]
+fn test_cpu_scan() {
+    let mut backend = CpuBackend::new(4);
+    backend.prepare(&ScanTarget::default()).unwrap();
+
+    let reporter = CollectHits::default();
+    let range = SEED - 200_000..SEED + 100_003;
+    assert_eq!(backend.scan(range.clone(), &reporter), Ok(300_003));
+    assert_eq!(reporter.scanned(), 300_003);
+    assert_eq!(reporter.into_hits(), vec![SEED]);
+}
+
+#[test]
+fn test_cpu_stop() {
+    struct Stopped;
+    impl ScanReporter for Stopped {
+        fn hit(&self, _seed: u64) {
+            panic!("a stopped scan should not check seeds");
+        }
+
+        fn progress(&self, _worker: usize, _seeds: u64) {}
+
+        fn should_stop(&self) -> bool {
+            true
+        }
+    }
+
+    let mut backend = CpuBackend::default();
+    backend.prepare(&ScanTarget::default()).unwrap();
+    assert_eq!(backend.scan(SEED..SEED + 1, &Stopped), Ok(0));
+}
+
+#[test]
+fn test_cpu_unprepared() {
+    assert!(CpuBackend::default()
+        .scan(0..1, &CollectHits::default())
+        .is_err());
+    assert!(CpuBackend::new(0).prepare(&ScanTarget::default()).is_err());
+}
+
+#[test]
+fn test_target_is_exact() {
+    assert!(ScanTarget::default().is_exact());
+    let mut partial = TreasureTarget::default();
+    partial.loot.slots[13] = None;
+    assert!(!ScanTarget::BuriedTreasure(partial).is_exact());
+}
+
+#[test]
+fn test_work_queue() {
+    let queue = WorkQueue::new(10..35, 10);
+    assert_eq!(queue.next_chunk(), Some(10..20));
+    assert_eq!(queue.next_chunk(), Some(20..30));
+    assert_eq!(queue.next_chunk(), Some(30..35));
+    assert_eq!(queue.next_chunk(), None);
+    assert_eq!(queue.next_chunk(), None);
+
+    let queue = WorkQueue::new(0..100, 10);
+    assert_eq!(queue.next_chunk(), Some(0..10));
+    queue.cancel();
+    assert_eq!(queue.next_chunk(), None);
+}
+
+#[test]
+fn test_work_queue_end_of_range() {
+    let queue = WorkQueue::new(u64::MAX - 25..u64::MAX, 10);
+    assert_eq!(queue.next_chunk(), Some(u64::MAX - 25..u64::MAX - 15));
+    assert_eq!(queue.next_chunk(), Some(u64::MAX - 15..u64::MAX - 5));
+    assert_eq!(queue.next_chunk(), Some(u64::MAX - 5..u64::MAX));
+    for _ in 0..4 {
+        assert_eq!(queue.next_chunk(), None);
+    }
+}
+
+#[test]
+fn test_progress_tracker() {
+    let progress = ProgressTracker::new(2, 1000);
+    progress.add_seeds(0, 100);
+    progress.add_seeds(1, 150);
+    progress.add_seeds(0, 250);
+    progress.add_hit();
+
+    let snapshot = ProgressSnapshot {
+        elapsed: Duration::from_secs(2),
+        ..progress.snapshot()
+    };
+    assert_eq!(snapshot.scanned, 500);
+    assert_eq!(snapshot.hits, 1);
+    assert_eq!(snapshot.percent(), 50.0);
+    assert_eq!(snapshot.rate(), 250.0);
+    assert_eq!(snapshot.eta(), Some(Duration::from_secs(2)));
+    assert_eq!(
+        snapshot.to_json_line(),
+        "{\"scanned\":500,\"total\":1000,\"percent\":50.0000,\"rate\":250.0,\"eta_secs\":2.000,\"hits\":1,\"elapsed_secs\":2.000}"
+    );
+}
+
+#[test]
+fn test_progress_eta_overflow() {
+    let progress = ProgressTracker::new(1, u64::MAX);
+    progress.add_seeds(0, 1);
+    let snapshot = ProgressSnapshot {
+        elapsed: Duration::from_secs(1 << 40),
+        ..progress.snapshot()
+    };
+    assert_eq!(snapshot.eta(), None);
+    assert!(snapshot.to_json_line().contains("\"eta_secs\":null"));
+}
@@ -0,0 +1,64 @@
+use seedcracker::bedrock::{parse_layer, BedrockCell, BedrockCracker, BedrockPattern};
+use seedcracker::pos::ChunkPos;
+use seedcracker::random::mcversion::{V1_12_2, V1_16_5, V1_18};
+
+const LAYER_4: &str = "
+    ..#....#......#.
+    ....##....#.....
+    ...#.#.....#...#
+    ............##..
+    .#.....#.#.#....
+    ..........#.....
+    ...........#....
+    ###..#..#....#..
+    #.#.....##.....#
+    ##...#........##
+    ...#...##.......
+    ..#.....####.##.
+    #.......#.....#.
+    ............#...
+    .#..#.......#..#
+    ..##....#.#..#..
+";
+
+#[test]
+fn test_pattern() {
+    let chunk = ChunkPos::new(-28, -73);
+    let pattern = BedrockPattern::generate(chunk, &V1_16_5).unwrap();
+    for (x, z, column) in [(0, 0, 7), (15, 0, 3), (0, 15, 7), (7, 9, 5)] {
+        for y in 0..5 {
+            assert_eq!(pattern.is_bedrock(x, y, z), column >> y & 1 != 0);
+        }
+    }
+
+    let legacy = BedrockPattern::generate(chunk, &V1_12_2).unwrap();
+    for (x, z, column) in [(0, 0, 3), (15, 0, 27), (0, 15, 9), (7, 9, 21)] {
+        for y in 0..5 {
+            assert_eq!(legacy.is_bedrock(x, y, z), column >> y & 1 != 0);
+        }
+    }
+    assert_eq!(BedrockPattern::generate(chunk, &V1_18), None);
+}
+
+#[test]
+fn test_parse_layer() {
+    let cells = parse_layer(4, LAYER_4).unwrap();
+    assert_eq!(cells.len(), 256);
+    assert_eq!(cells[2], BedrockCell::new(2, 4, 0, true));
+    assert!(parse_layer(4, "#.?").is_err());
+}
+
+#[test]
+fn test_find_chunks() {
+    // Only the top left quarter of the layer was visible.
+    let cells: Vec<BedrockCell> = parse_layer(4, LAYER_4)
+        .unwrap()
+        .into_iter()
+        .filter(|cell| cell.x < 8 && cell.z < 8)
+        .collect();
+    let cracker = BedrockCracker::new(cells, V1_16_5);
+    assert_eq!(
+        cracker.find_chunks(ChunkPos::new(-40, -80), ChunkPos::new(0, -60)),
+        vec![ChunkPos::new(-28, -73)]
+    );
+}
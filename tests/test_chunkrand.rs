@@ -1,11 +1,17 @@
+use seedcracker::pos::{BlockPos, ChunkPos, RegionPos};
 use seedcracker::random::chunkrand::ChunkRand;
 use seedcracker::random::jrand::JRand;
+use seedcracker::random::mcversion::{V1_12_2, V1_18};
+use seedcracker::random::xoroshiro::Xoroshiro;
 
 #[test]
 fn test_terrain_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
-    assert_eq!(chunk_rand.set_terrain_seed(54, 34), 22979680526842);
+    assert_eq!(
+        chunk_rand.set_terrain_seed(ChunkPos::new(54, 34)),
+        22979680526842
+    );
 }
 
 #[test]
@@ -13,7 +19,7 @@ fn test_population_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(
-        chunk_rand.set_population_seed(5235023985902385, 234, 256),
+        chunk_rand.set_population_seed(5235023985902385, BlockPos::new(234, 0, 256)),
         72830530974919
     );
 }
@@ -23,7 +29,7 @@ fn test_decorator_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(
-        chunk_rand.set_decorator_seed_block_salt(571437852345, 10, 25, 5),
+        chunk_rand.set_decorator_seed_block_salt(571437852345, BlockPos::new(10, 0, 25), 5),
         234602471937219
     );
 }
@@ -33,7 +39,7 @@ fn test_carver_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(
-        chunk_rand.set_carver_seed(5714378552345, 55, 6),
+        chunk_rand.set_carver_seed(5714378552345, ChunkPos::new(55, 6)),
         57433805167245
     );
 }
@@ -43,7 +49,7 @@ fn test_region_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(
-        chunk_rand.set_region_seed(5714378552345, 55, 6, 53415),
+        chunk_rand.set_region_seed(5714378552345, RegionPos::new(55, 6), 53415),
         25314788610166
     );
 }
@@ -53,7 +59,7 @@ fn test_weak_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(
-        chunk_rand.set_weak_seed(7432895798243759, 34, 3),
+        chunk_rand.set_weak_seed(7432895798243759, ChunkPos::new(34, 3)),
         114546403766701
     );
 }
@@ -63,7 +69,7 @@ fn test_slime_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(
-        chunk_rand.set_slime_seed_scramble(7432895798243759, 34, 3, 5555),
+        chunk_rand.set_slime_seed_scramble(7432895798243759, ChunkPos::new(34, 3), 5555),
         114548116864026
     )
 }
@@ -73,7 +79,7 @@ fn test_position_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(
-        chunk_rand.set_position_seed(555, 54, 1345),
+        chunk_rand.set_position_seed(BlockPos::new(555, 54, 1345)),
         27536523298125
     )
 }
@@ -83,8 +89,8 @@ fn test_base_stone_seed() {
     let rand = JRand::new(4506419895);
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(
-        chunk_rand.set_base_stone_seed(4327857727348957239, 399, 45, 399),
-        515723718367701839
+        chunk_rand.set_base_stone_seed(4327857727348957239, BlockPos::new(399, 45, 399)),
+        -1048058806902065195
     )
 }
 
@@ -94,3 +100,108 @@ fn test_random_item() {
     let mut chunk_rand: ChunkRand = rand.into();
     assert_eq!(chunk_rand.get_random(&[1, 2, 3, 4, 5]), 1)
 }
+
+#[test]
+fn test_decorator_seed_1_12() {
+    let mut chunk_rand = ChunkRand::default().with_version(V1_12_2);
+    assert_eq!(
+        chunk_rand.set_decorator_seed_block_salt(
+            8675309123456789,
+            ChunkPos::new(-28, -73).get_min_block(),
+            30001
+        ),
+        8770575769602
+    );
+    assert_eq!(chunk_rand.get_next_long(), 8460278558483344372);
+}
+
+#[test]
+fn test_population_seed_world_border() {
+    const WORLD_SEED: i64 = 8675309123456789;
+    let block = BlockPos::new(29_999_984, 0, -29_999_984);
+
+    // The products wrap like Java's `long` arithmetic this far out.
+    let mut rand = JRand::new(WORLD_SEED);
+    let a = rand.get_next_long() | 1;
+    let b = rand.get_next_long() | 1;
+    let population_seed = (block.x as i64)
+        .wrapping_mul(a)
+        .wrapping_add((block.z as i64).wrapping_mul(b))
+        ^ WORLD_SEED;
+
+    let mut chunk_rand = ChunkRand::default();
+    assert_eq!(
+        chunk_rand.set_population_seed(WORLD_SEED, block),
+        population_seed & ((1 << 48) - 1)
+    );
+    let mut chunk_rand = ChunkRand::default().with_version(V1_18);
+    chunk_rand.set_population_seed(WORLD_SEED, block);
+}
+
+#[test]
+fn test_decorator_seed_1_12_ignores_salt() {
+    // Every pre-1.13 decorator starts over from the population random.
+    let mut chunk_rand = ChunkRand::default().with_version(V1_12_2);
+    let block = ChunkPos::new(-28, -73).get_min_block();
+    let population_seed = chunk_rand.set_population_seed(8675309123456789, block);
+    let first = chunk_rand.get_next_long();
+    for salt in [0, 5, 30001] {
+        assert_eq!(
+            chunk_rand.set_decorator_seed(population_seed, salt),
+            population_seed
+        );
+        assert_eq!(chunk_rand.get_next_long(), first);
+    }
+}
+
+/// `WorldgenRandom.nextLong` over a `XoroshiroRandomSource`: `java.util.Random.nextLong`
+/// on `next(bits) = (int) (source.nextLong() >>> (64 - bits))`.
+fn worldgen_next_long(source: &mut Xoroshiro) -> i64 {
+    let high = (source.get_next_long() as u64 >> 32) as i32;
+    let low = (source.get_next_long() as u64 >> 32) as i32;
+    ((high as i64) << 32) + low as i64
+}
+
+#[test]
+fn test_decorator_seed_1_18() {
+    const WORLD_SEED: i64 = 8675309123456789;
+    let block = ChunkPos::new(-28, -73).get_min_block();
+
+    // `WorldgenRandom.setDecorationSeed` spelled out on the raw source.
+    let mut source = Xoroshiro::new(WORLD_SEED);
+    let a = worldgen_next_long(&mut source) | 1;
+    let b = worldgen_next_long(&mut source) | 1;
+    let population_seed = (block.x as i64)
+        .wrapping_mul(a)
+        .wrapping_add((block.z as i64).wrapping_mul(b))
+        ^ WORLD_SEED;
+
+    let mut chunk_rand = ChunkRand::default().with_version(V1_18);
+    assert_eq!(
+        chunk_rand.set_population_seed(WORLD_SEED, block),
+        population_seed
+    );
+    assert_eq!(
+        chunk_rand.set_decorator_seed_block_salt(WORLD_SEED, block, 30001),
+        population_seed + 30001
+    );
+    let mut source = Xoroshiro::new(population_seed + 30001);
+    assert_eq!(chunk_rand.get_next_long(), worldgen_next_long(&mut source));
+
+    // Bounded ints reject on `next(31)` like `java.util.Random`, rather than taking the
+    // source's own `nextInt(bound)`.
+    chunk_rand.set_xoroshiro_seed(WORLD_SEED);
+    let mut source = Xoroshiro::new(WORLD_SEED);
+    for bound in [7, 16, 100, 1 << 30] {
+        let bits = (source.get_next_long() as u64 >> 33) as i32;
+        let expected = if bound & -bound == bound {
+            ((bound as i64 * bits as i64) >> 31) as i32
+        } else {
+            // None of these bounds is close enough to 2^31 to reject anything here.
+            bits % bound
+        };
+        assert_eq!(chunk_rand.get_next_int_bound(bound), expected);
+    }
+    let bits = (source.get_next_long() as u64 >> 40) as i32;
+    assert_eq!(chunk_rand.get_next_float(), bits as f32 / (1 << 24) as f32);
+}
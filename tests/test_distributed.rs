@@ -0,0 +1,128 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::Range;
+use std::thread;
+use std::time::Duration;
+
+use seedcracker::backend::cpu::CpuBackend;
+use seedcracker::backend::TreasureTarget;
+use seedcracker::distributed::coordinator::Coordinator;
+use seedcracker::distributed::worker::run_worker;
+use seedcracker::distributed::{Request, Response, WorkUnit};
+
+const SEED: u64 = -4872636734044769429_i64 as u64;
+const RANGE: Range<u64> = SEED - 250_000..SEED + 250_000;
+
+fn listen() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    (listener, addr)
+}
+
+#[test]
+fn test_protocol_lines() {
+    let unit = Response::Unit(WorkUnit {
+        id: 3,
+        range: 10..20,
+    });
+    assert_eq!(unit.to_line(), "UNIT 3 10 20");
+    assert_eq!(Response::parse("UNIT 3 10 20"), Ok(unit));
+    assert_eq!(
+        Response::parse(
+            &Response::Hello {
+                target: TreasureTarget::default()
+            }
+            .to_line()
+        ),
+        Ok(Response::Hello {
+            target: TreasureTarget::default()
+        })
+    );
+    assert_eq!(
+        Request::parse("COMPLETE 4 100"),
+        Ok(Request::Complete {
+            unit: 4,
+            scanned: 100
+        })
+    );
+    assert!(Request::parse("HIT 4").is_err());
+    assert!(Response::parse("HELLO seedcracker/0 1 2").is_err());
+
+    let mut partial = TreasureTarget::default();
+    partial.loot.slots[3] = None;
+    let line = Response::Hello { target: partial }.to_line();
+    assert!(line.starts_with(
+        "HELLO seedcracker/2 -28 -73 minecraft:leather_chestplate*1 minecraft:iron_ingot*2 \
+         minecraft:iron_ingot*1 ? minecraft:gold_ingot*1 minecraft:cooked_cod*1 empty "
+    ));
+    assert_eq!(
+        Response::parse(&line),
+        Ok(Response::Hello { target: partial })
+    );
+    assert_eq!(
+        Response::parse(&line.replace("minecraft:cooked_cod*1", "minecraft:cooked_cod*65")),
+        Err(format!(
+            "65 minecraft:cooked_cod is more than a stack of 64 in `{}`",
+            line.replace("minecraft:cooked_cod*1", "minecraft:cooked_cod*65")
+        ))
+    );
+}
+
+#[test]
+fn test_workers() {
+    let coordinator = Coordinator::new(
+        TreasureTarget::default(),
+        RANGE,
+        60_000,
+        Duration::from_secs(60),
+    );
+    let (listener, addr) = listen();
+    thread::scope(|scope| {
+        scope.spawn(|| coordinator.serve(listener).unwrap());
+        let workers: Vec<_> = (0..2)
+            .map(|_| scope.spawn(|| run_worker(&addr, &mut CpuBackend::new(1)).unwrap()))
+            .collect();
+        let units: u64 = workers.into_iter().map(|w| w.join().unwrap()).sum();
+        assert_eq!(units, 9);
+    });
+    assert!(coordinator.is_done());
+    assert_eq!(coordinator.hits(), vec![SEED]);
+    assert_eq!(coordinator.scanned(), RANGE.end - RANGE.start);
+}
+
+#[test]
+fn test_release_on_disconnect() {
+    let coordinator = Coordinator::new(
+        TreasureTarget::default(),
+        RANGE,
+        250_000,
+        Duration::from_secs(60),
+    );
+    let (listener, addr) = listen();
+    thread::scope(|scope| {
+        scope.spawn(|| coordinator.serve(listener).unwrap());
+
+        // Lease the unit holding the seed, report a hit that never gets confirmed, and
+        // drop the connection without completing it.
+        let stream = TcpStream::connect(&addr).unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        for request in [
+            Request::Lease,
+            Request::Hit {
+                unit: 0,
+                seed: SEED - 1,
+            },
+        ] {
+            writeln!(writer, "{}", request.to_line()).unwrap();
+            line.clear();
+            reader.read_line(&mut line).unwrap();
+        }
+        drop((reader, writer));
+
+        assert_eq!(run_worker(&addr, &mut CpuBackend::new(1)), Ok(2));
+    });
+    assert_eq!(coordinator.hits(), vec![SEED]);
+}
@@ -0,0 +1,73 @@
+use seedcracker::end_pillars::{get_pillar_seed, get_pillars, lift_pillar_seed, EndPillarCracker};
+use seedcracker::observation::LootTarget;
+use seedcracker::pos::ChunkPos;
+use seedcracker::random::mth::MASK_48;
+use seedcracker::{check_seed, target_loot};
+
+const WORLD_SEED: i64 = 8675309123456789;
+
+const HEIGHTS: [i32; 10] = [85, 76, 82, 103, 94, 88, 91, 100, 97, 79];
+
+#[test]
+fn test_pillars() {
+    assert_eq!(get_pillar_seed(WORLD_SEED), 46495);
+    assert_eq!(get_pillar_seed(-4872636734044769429), 46977);
+    let pillars = get_pillars(WORLD_SEED);
+    assert_eq!(pillars.map(|pillar| pillar.height), HEIGHTS);
+    assert_eq!(
+        pillars.map(|pillar| (pillar.center_x, pillar.center_z)),
+        [
+            (42, 0),
+            (33, 24),
+            (12, 39),
+            (-13, 39),
+            (-34, 24),
+            (-42, -1),
+            (-34, -25),
+            (-13, -40),
+            (12, -40),
+            (33, -25)
+        ]
+    );
+    assert!(pillars[2].guarded && pillars[9].guarded);
+    assert_eq!(pillars[3].radius, 5);
+}
+
+#[test]
+fn test_pillar_seeds() {
+    let cracker = EndPillarCracker::new(HEIGHTS.map(Some)).unwrap();
+    assert_eq!(cracker.pillar_seeds(), [46495]);
+    assert!(cracker.check(WORLD_SEED));
+
+    let mut heights = HEIGHTS.map(Some);
+    heights[0] = None;
+    heights[1] = None;
+    let cracker = EndPillarCracker::new(heights).unwrap();
+    assert!(cracker.pillar_seeds().contains(&46495));
+
+    assert!(EndPillarCracker::new([Some(77); 10]).is_none());
+}
+
+#[test]
+fn test_lift_pillar_seed() {
+    let candidates = lift_pillar_seed(46495);
+    assert!(candidates.contains(&(WORLD_SEED & 0xFFFFFFFF)));
+    for low_bits in candidates.iter().step_by(97) {
+        assert_eq!(get_pillar_seed(*low_bits), 46495);
+    }
+}
+
+#[test]
+fn test_crack_with_buried_treasure() {
+    let world_seed = -4872636734044769429_i64;
+    let structure_seed = (world_seed & MASK_48) as u64;
+    let cracker =
+        EndPillarCracker::new(get_pillars(world_seed).map(|pillar| Some(pillar.height))).unwrap();
+    let target = LootTarget::exact(&target_loot());
+    let upper = structure_seed >> 32;
+    let found: Vec<u64> = cracker
+        .structure_seeds_in(upper - 1..upper + 2)
+        .filter(|&seed| check_seed(seed, ChunkPos::new(-28, -73), &target))
+        .collect();
+    assert_eq!(found, [structure_seed]);
+}
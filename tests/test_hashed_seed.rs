@@ -0,0 +1,37 @@
+use seedcracker::hashed_seed::{
+    filter_world_seeds, find_world_seeds, hash_world_seed, verify_world_seed,
+};
+use seedcracker::random::mcversion::{V1_14_4, V1_16_5};
+use seedcracker::random::mth::MASK_48;
+
+#[test]
+fn test_hash_world_seed() {
+    assert_eq!(hash_world_seed(0), 8794265229978523055);
+    assert_eq!(hash_world_seed(8675309123456789), -8380507278433315825);
+    assert_eq!(hash_world_seed(-4872636734044769429), 3527904291304933301);
+}
+
+#[test]
+fn test_verify_world_seed() {
+    assert!(verify_world_seed(0, 8794265229978523055, &V1_16_5));
+    assert!(!verify_world_seed(0, 8794265229978523055, &V1_14_4));
+    assert!(!verify_world_seed(1, 8794265229978523055, &V1_16_5));
+}
+
+#[test]
+fn test_find_world_seeds() {
+    let structure_seed = (-4872636734044769429_i64 & MASK_48) as u64;
+    assert_eq!(
+        find_world_seeds(structure_seed, 3527904291304933301, &V1_16_5),
+        [-4872636734044769429]
+    );
+    assert!(find_world_seeds(structure_seed, 3527904291304933301, &V1_14_4).is_empty());
+}
+
+#[test]
+fn test_filter_world_seed_window() {
+    let world_seed = -4872636734044769429_i64;
+    let window = world_seed - (1 << 12)..world_seed + (1 << 12);
+    let found: Vec<i64> = filter_world_seeds(window, 3527904291304933301, &V1_16_5).collect();
+    assert_eq!(found, [world_seed]);
+}
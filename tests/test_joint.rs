@@ -0,0 +1,105 @@
+use seedcracker::backend::cpu::CpuBackend;
+use seedcracker::backend::{Backend, CollectHits, ScanTarget};
+use seedcracker::joint::{Clue, JointCracker};
+use seedcracker::loot::tables::VILLAGE_WEAPONSMITH;
+use seedcracker::observation::LootTarget;
+use seedcracker::pos::{ChunkPos, RegionPos};
+use seedcracker::random::mcversion::{V1_17_1, V1_18};
+use seedcracker::structure::{Structure, StructureChest};
+use seedcracker::village::{get_village_chunk, VillageCracker};
+use seedcracker::{can_spawn_buried_treasure, check_seed, target_loot};
+
+const SEED: u64 = -4872636734044769429_i64 as u64;
+const CHUNK: ChunkPos = ChunkPos::new(-28, -73);
+
+fn treasure(chunk: ChunkPos, seed: u64) -> Clue {
+    let chest = StructureChest::new(Structure::BuriedTreasure, chunk, 0);
+    Clue::BuriedTreasure {
+        chest,
+        target: LootTarget::exact(&chest.get_loot(seed as i64, true)),
+    }
+}
+
+/// A second chunk the seed also has buried treasure in.
+fn second_chunk() -> ChunkPos {
+    (0..)
+        .map(|x| ChunkPos::new(x, 40))
+        .find(|&chunk| can_spawn_buried_treasure(SEED, chunk))
+        .unwrap()
+}
+
+#[test]
+fn test_clue_matches_check_seed() {
+    let target = LootTarget::exact(&target_loot());
+    let clue = Clue::BuriedTreasure {
+        chest: StructureChest::new(Structure::BuriedTreasure, CHUNK, 0),
+        target,
+    };
+    for seed in SEED - 2000..SEED + 2000 {
+        assert_eq!(clue.check(seed), check_seed(seed, CHUNK, &target));
+    }
+}
+
+#[test]
+fn test_rejects_1_18() {
+    // From 1.18 on the loot depends on all 64 bits of the world seed.
+    let chest = StructureChest::new(Structure::BuriedTreasure, CHUNK, 0).with_version(V1_18);
+    let clue = Clue::BuriedTreasure {
+        chest,
+        target: LootTarget::unknown(),
+    };
+    assert!(JointCracker::new(vec![treasure(CHUNK, SEED), clue]).is_err());
+    let chest = chest.with_version(V1_17_1);
+    let clue = Clue::BuriedTreasure {
+        chest,
+        target: LootTarget::unknown(),
+    };
+    assert!(JointCracker::new(vec![clue]).is_ok());
+}
+
+#[test]
+fn test_driver() {
+    let chest = StructureChest::new(Structure::Village, ChunkPos::new(11, 9), 0)
+        .with_loot_table(&VILLAGE_WEAPONSMITH);
+    let target = chest.get_loot(SEED as i64, true);
+    let plain = Clue::Chest {
+        chest,
+        target: LootTarget::exact(&target),
+    };
+    let cracker = JointCracker::new(vec![plain, treasure(CHUNK, SEED)]).unwrap();
+    assert!(matches!(cracker.driver(), Clue::BuriedTreasure { .. }));
+    assert!((0.005..0.015).contains(&cracker.driver().pass_rate()));
+
+    let start = get_village_chunk(SEED as i64, RegionPos::new(0, 0));
+    let village = Clue::Village(VillageCracker::new(
+        Some(start),
+        ChunkPos::new(11, 9),
+        0,
+        &VILLAGE_WEAPONSMITH,
+        LootTarget::exact(&target),
+    ));
+    let cracker = JointCracker::new(vec![treasure(CHUNK, SEED), plain, village]).unwrap();
+    assert!(matches!(cracker.driver(), Clue::Village(_)));
+    assert!(matches!(cracker.clues()[2], Clue::Chest { .. }));
+    assert!(cracker.check(SEED));
+
+    assert!(JointCracker::new(Vec::new()).is_err());
+}
+
+#[test]
+fn test_joint_scan() {
+    let second = second_chunk();
+    let cracker = JointCracker::new(vec![treasure(CHUNK, SEED), treasure(second, SEED)]).unwrap();
+    let mut backend = CpuBackend::new(2);
+    backend.prepare(&ScanTarget::Joint(cracker)).unwrap();
+    assert_eq!(backend.name(), "joint-cpu");
+    let hits = CollectHits::default();
+    let range = SEED - 100_000..SEED + 100_000;
+    assert_eq!(backend.scan(range.clone(), &hits), Ok(200_000));
+    assert_eq!(hits.into_hits(), vec![SEED]);
+
+    // Another seed's loot in the second chest rules the seed out.
+    let cracker =
+        JointCracker::new(vec![treasure(CHUNK, SEED), treasure(second, SEED + 1)]).unwrap();
+    assert!(!cracker.check(SEED));
+}
@@ -1,4 +1,4 @@
-use seedcracker::random::jrand::JRand;
+use seedcracker::random::jrand::{JRand, LCG};
 
 #[test]
 fn test_next_int_bound() {
@@ -18,6 +18,30 @@ fn test_next_seed() {
     assert_eq!(rand.next_seed(), 281474976500957)
 }
 
+#[test]
+fn test_lcg_next_seed() {
+    // Every LCG steps with its own constants, not `JAVA`'s.
+    assert_eq!(LCG::MS_VISUAL_C.next_seed(1), 214013 + 2531011);
+    assert_eq!(LCG::MS_VISUAL_C.next_seed(1 << 32), 2531011);
+    assert_eq!(LCG::MMIX.next_seed(0), 1442695040888963407);
+    assert_eq!(LCG::XKCD.next_seed(12345), 4);
+
+    let skip = LCG::JAVA.combine_steps(3);
+    let mut seed = 4506419895;
+    for _ in 0..3 {
+        seed = LCG::JAVA.next_seed(seed);
+    }
+    assert_eq!(skip.next_seed(4506419895), seed);
+
+    let mut rand = JRand::new(4506419895);
+    let mut replay = rand;
+    rand.advance_calls(3);
+    for _ in 0..3 {
+        replay.next_seed();
+    }
+    assert_eq!(rand.seed, replay.seed);
+}
+
 #[test]
 fn test_next_float() {
     let mut rand = JRand::new(4506419895);
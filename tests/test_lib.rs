@@ -1,35 +1,37 @@
+use seedcracker::observation::LootTarget;
+use seedcracker::pos::ChunkPos;
 use seedcracker::random::mth::MASK_48;
-use seedcracker::{can_spawn_buried_treasure, check_seed};
+use seedcracker::{can_spawn_buried_treasure, check_seed, target_loot};
 
 #[test]
 fn test_check_seed() {
-    assert!(check_seed((-4872636734044769429_i64) as u64, -28, -73));
-    assert!(!check_seed((-4872636734044769428_i64) as u64, -28, -73));
-    assert!(!check_seed(7823457984237234584_i64 as u64, -28, -73));
+    const CHUNK: ChunkPos = ChunkPos::new(-28, -73);
+    const SEED: u64 = -4872636734044769429_i64 as u64;
+    let target = LootTarget::exact(&target_loot());
+
+    assert!(check_seed(SEED, CHUNK, &target));
+    assert!(!check_seed(SEED + 1, CHUNK, &target));
+    assert!(!check_seed(7823457984237234584_i64 as u64, CHUNK, &target));
+
+    // Slots that were not seen match anything.
+    let mut partial = target;
+    partial.slots[..20].fill(None);
+    assert!(check_seed(SEED, CHUNK, &partial));
 }
 
 #[test]
 fn test_valid_structure_pos() {
-    const CHUNK_X: i32 = -28;
-    const CHUNK_Z: i32 = -73;
+    const CHUNK: ChunkPos = ChunkPos::new(-28, -73);
 
     let lower48 = ((-4872636734044769429) & MASK_48) as u64;
-    assert!(can_spawn_buried_treasure(lower48, CHUNK_X, CHUNK_Z));
+    assert!(can_spawn_buried_treasure(lower48, CHUNK));
     for i in 0..0x10000 {
-        assert!(can_spawn_buried_treasure(
-            lower48 | (i << 48),
-            CHUNK_X,
-            CHUNK_Z
-        ));
+        assert!(can_spawn_buried_treasure(lower48 | (i << 48), CHUNK));
     }
 
     let lower48 = ((-754238579824375972) & MASK_48) as u64;
-    assert!(!can_spawn_buried_treasure(lower48, CHUNK_X, CHUNK_Z));
+    assert!(!can_spawn_buried_treasure(lower48, CHUNK));
     for i in 0..0x10000 {
-        assert!(!can_spawn_buried_treasure(
-            lower48 | (i << 48),
-            CHUNK_X,
-            CHUNK_Z
-        ));
+        assert!(!can_spawn_buried_treasure(lower48 | (i << 48), CHUNK));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,214 @@
+use seedcracker::item::Item::*;
+use seedcracker::loot::enchantment::Enchantment;
+use seedcracker::loot::tables::{
+    BASTION_TREASURE, BURIED_TREASURE, END_CITY_TREASURE, WOODLAND_MANSION,
+};
+use seedcracker::loot::LootFunction::{EnchantRandomly, SetDamage};
+use seedcracker::loot::{LootEntry, LootPool, LootTable};
+use seedcracker::pos::ChunkPos;
+use seedcracker::random::chunkrand::ChunkRand;
+use seedcracker::random::jrand::JRand;
+use seedcracker::random::mcversion::{V1_12, V1_13, V1_16_5, V1_17_1, V1_18, V1_18_2};
+use seedcracker::random::mth::MASK_48;
+use seedcracker::random::xoroshiro::Xoroshiro;
+use seedcracker::structure::{Structure, StructureChest};
+use seedcracker::{InventoryVec, ItemStack};
+
+const TARGET: [(seedcracker::item::Item, u8); 27] = [
+    (LeatherChestplate, 1),
+    (IronIngot, 2),
+    (IronIngot, 1),
+    (GoldIngot, 1),
+    (GoldIngot, 1),
+    (CookedCod, 1),
+    (Empty, 0),
+    (IronIngot, 1),
+    (IronIngot, 1),
+    (Diamond, 1),
+    (GoldIngot, 1),
+    (CookedCod, 2),
+    (GoldIngot, 1),
+    (Diamond, 1),
+    (HeartOfTheSea, 1),
+    (CookedSalmon, 1),
+    (IronIngot, 1),
+    (Emerald, 6),
+    (IronIngot, 1),
+    (GoldIngot, 1),
+    (CookedSalmon, 2),
+    (TNT, 1),
+    (CookedSalmon, 1),
+    (GoldIngot, 1),
+    (IronIngot, 1),
+    (GoldIngot, 1),
+    (Emerald, 1),
+];
+
+#[test]
+fn test_buried_treasure_table() {
+    let chest = StructureChest::new(Structure::BuriedTreasure, ChunkPos::new(-28, -73), 0);
+    assert_eq!(chest.loot_table, &BURIED_TREASURE);
+    let target: InventoryVec<ItemStack> = TARGET
+        .iter()
+        .map(|&(item, count)| ItemStack::new(item, count))
+        .collect();
+    assert_eq!(chest.get_loot(-4872636734044769429, true), target);
+    assert!(chest.check(-4872636734044769429, &target));
+    assert!(!chest.check(-4872636734044769428, &target));
+}
+
+#[test]
+fn test_structure_salts() {
+    assert_eq!(Structure::BuriedTreasure.salt(), 30001);
+    assert_eq!(Structure::WoodlandMansion.salt(), 40001);
+    assert_eq!(Structure::EndCity.salt(), 40010);
+    assert_eq!(Structure::BastionRemnant.salt(), 40012);
+}
+
+#[test]
+fn test_chest_index() {
+    let first = StructureChest::new(Structure::EndCity, ChunkPos::new(12, -7), 0);
+    let second = StructureChest::new(Structure::EndCity, ChunkPos::new(12, -7), 1);
+    assert_ne!(
+        first.get_loot_seed(123456789),
+        second.get_loot_seed(123456789)
+    );
+    for seed in 0..256 {
+        let loot = END_CITY_TREASURE.get_loot(seed, false);
+        assert!((2..=6).contains(&loot.len()));
+        let loot = WOODLAND_MANSION.get_loot(seed, true);
+        assert_eq!(loot.len(), 27);
+        assert!(loot.iter().all(|stack| stack.get_item() != Book));
+    }
+}
+
+#[test]
+fn test_enchantment_compatibility() {
+    assert!(!Enchantment::Protection.is_compatible_with(&Enchantment::FireProtection));
+    assert!(Enchantment::Protection.is_compatible_with(&Enchantment::FeatherFalling));
+    assert!(!Enchantment::Sharpness.is_compatible_with(&Enchantment::Smite));
+    assert!(!Enchantment::Mending.is_compatible_with(&Enchantment::Infinity));
+    assert!(Enchantment::Unbreaking.is_compatible_with(&Enchantment::Mending));
+}
+
+fn to_inventory(items: &[(seedcracker::item::Item, u8); 27]) -> InventoryVec<ItemStack> {
+    items
+        .iter()
+        .map(|&(item, count)| ItemStack::new(item, count))
+        .collect()
+}
+
+#[test]
+fn test_buried_treasure_versions() {
+    let seed = -4872636734044769429;
+    let chunk = ChunkPos::new(-28, -73);
+    let chest = StructureChest::new(Structure::BuriedTreasure, chunk, 0);
+    let block = chunk.get_min_block();
+    let salt = Structure::BuriedTreasure.salt() as i64;
+
+    // `setDecorationSeed` and `setFeatureSeed` spelled out on `java.util.Random`, the
+    // same for every release from 1.13 to 1.17.
+    let mut rand = JRand::new(seed);
+    let a = rand.get_next_long() | 1;
+    let b = rand.get_next_long() | 1;
+    let population_seed = (block.x as i64)
+        .wrapping_mul(a)
+        .wrapping_add((block.z as i64).wrapping_mul(b))
+        ^ seed;
+    let loot_seed = JRand::new(population_seed + salt).get_next_long();
+    for version in [V1_13, V1_16_5, V1_17_1] {
+        let chest = chest.with_version(version);
+        assert_eq!(chest.loot_table, &BURIED_TREASURE);
+        assert_eq!(chest.get_loot_seed(seed), loot_seed);
+    }
+    assert_eq!(
+        chest.with_version(V1_16_5).get_loot(seed, true),
+        to_inventory(&TARGET)
+    );
+
+    // From 1.18 on the same steps run on a `Xoroshiro` source, whose `nextLong` goes
+    // through `next(32)` twice like `java.util.Random`.
+    let next_long = |source: &mut Xoroshiro| {
+        let high = (source.get_next_long() as u64 >> 32) as i32;
+        let low = (source.get_next_long() as u64 >> 32) as i32;
+        ((high as i64) << 32) + low as i64
+    };
+    let mut source = Xoroshiro::new(seed);
+    let a = next_long(&mut source) | 1;
+    let b = next_long(&mut source) | 1;
+    let population_seed = (block.x as i64)
+        .wrapping_mul(a)
+        .wrapping_add((block.z as i64).wrapping_mul(b))
+        ^ seed;
+    let loot_seed = next_long(&mut Xoroshiro::new(population_seed + salt));
+    for version in [V1_18, V1_18_2] {
+        let chest = chest.with_version(version);
+        assert_eq!(chest.loot_table, &BURIED_TREASURE);
+        assert_eq!(chest.get_loot_seed(seed), loot_seed);
+    }
+}
+
+#[test]
+fn test_reverse_loot_seed() {
+    let structure_seed = -4872636734044769429 & MASK_48;
+    let chunk = ChunkPos::new(-28, -73);
+    for chest in [
+        StructureChest::new(Structure::BuriedTreasure, chunk, 0).with_version(V1_16_5),
+        StructureChest::new(Structure::Village, chunk, 3).with_version(V1_13),
+        StructureChest::new(Structure::EndCity, ChunkPos::new(1875, -6), 1).with_version(V1_17_1),
+    ] {
+        let loot_seed = chest.get_loot_seed(structure_seed);
+        let seeds = chest.reverse_loot_seed(loot_seed).unwrap();
+        assert!(seeds.contains(&structure_seed));
+        assert!(seeds
+            .iter()
+            .all(|&seed| chest.get_loot_seed(seed) == loot_seed));
+    }
+
+    let chest = StructureChest::new(Structure::BuriedTreasure, chunk, 0);
+    assert!(chest.with_version(V1_18).reverse_loot_seed(1234).is_err());
+    assert!(chest.with_version(V1_12).reverse_loot_seed(1234).is_err());
+}
+
+#[test]
+fn test_bastion_gear() {
+    let gear: Vec<_> = BASTION_TREASURE.pools[0]
+        .entries
+        .iter()
+        .filter(|entry| entry.weight == 6 && !entry.functions.is_empty())
+        .collect();
+    assert_eq!(gear.len(), 5);
+    for entry in gear {
+        assert_eq!(entry.functions, [SetDamage(0.8, 1.0), EnchantRandomly(&[])]);
+    }
+
+    // A damaged, randomly enchanted sword rolls the damage, then the enchantment among
+    // those that fit a sword, then its level.
+    const SWORD: LootTable = LootTable::new(
+        "sword",
+        &[LootPool::new(
+            1,
+            1,
+            &[LootEntry::new(
+                DiamondSword,
+                1,
+                &[SetDamage(0.8, 1.0), EnchantRandomly(&[])],
+            )],
+        )],
+    );
+    let available: Vec<Enchantment> = Enchantment::ALL
+        .into_iter()
+        .filter(|enchantment| {
+            enchantment.is_discoverable() && enchantment.can_enchant(DiamondSword)
+        })
+        .collect();
+    for seed in 0..64 {
+        let mut rand = ChunkRand::new(seed);
+        SWORD.generate(&mut rand);
+        let mut replay = ChunkRand::new(seed);
+        replay.get_next_float();
+        let enchantment = available[replay.get_next_int_bound(available.len() as i32) as usize];
+        replay.get_int(1, enchantment.max_level());
+        assert_eq!(rand.get_next_long(), replay.get_next_long());
+    }
+}
@@ -0,0 +1,59 @@
+use seedcracker::random::mcversion::{
+    MCVersion, Stage, V1_16, V1_16_1, V1_16_5, V1_17, V1_18, V1_19_4, V1_20, V1_20_1, V1_20_2,
+    V1_21_10,
+};
+
+#[test]
+fn test_latest() {
+    assert_eq!(*MCVersion::latest(), V1_21_10);
+    assert_eq!(MCVersion::from_string("1.20.1"), Some(&V1_20_1));
+}
+
+#[test]
+fn test_parse_snapshot() {
+    let snapshot = MCVersion::parse("23w31a").unwrap();
+    assert_eq!(
+        snapshot.get_stage(),
+        Stage::Snapshot {
+            year: 23,
+            week: 31,
+            letter: b'a'
+        }
+    );
+    assert_eq!(snapshot.to_string(), "23w31a");
+    assert!(snapshot.is_newer_than(&V1_20_1));
+    assert!(snapshot.is_older_than(&V1_20_2));
+    assert!(snapshot.is_between(&V1_18, &V1_20_2));
+    assert!(MCVersion::parse("23w33a").unwrap().is_newer_than(&snapshot));
+    assert_eq!(MCVersion::parse("12w34a"), None);
+    assert_eq!(MCVersion::parse("23w3é"), None);
+    assert_eq!(MCVersion::parse("23w31"), None);
+}
+
+#[test]
+fn test_parse_pre_release() {
+    let pre = MCVersion::parse("1.20-pre1").unwrap();
+    let rc = MCVersion::parse("1.20 Release Candidate 1").unwrap();
+    assert_eq!(pre, MCVersion::parse("1.20 Pre-release 1").unwrap());
+    assert_eq!(rc.to_string(), "1.20-rc1");
+    assert!(pre.is_newer_than(&V1_19_4));
+    assert!(pre.is_older_than(&MCVersion::parse("1.20-pre2").unwrap()));
+    assert!(pre.is_older_than(&rc));
+    assert!(rc.is_older_than(&V1_20));
+    assert!(MCVersion::parse("23w18a").unwrap().is_older_than(&pre));
+    assert_eq!(pre.newer(), Some(&V1_20));
+    assert_eq!(pre.older(), Some(&V1_19_4));
+}
+
+#[test]
+fn test_ord_sub_version() {
+    // `Ord` used to compare sub versions the wrong way round, putting 1.16.5 before 1.16.
+    assert!(V1_16_5 > V1_16_1);
+    assert!(V1_16_1 > V1_16);
+    assert!(V1_17 > V1_16_5);
+    let mut versions = vec![V1_17, V1_16_5, V1_16, V1_16_1];
+    versions.sort();
+    assert_eq!(versions, [V1_16, V1_16_1, V1_16_5, V1_17]);
+    assert_eq!(versions.iter().max(), Some(&V1_17));
+    assert_eq!(V1_16_5.cmp(&V1_16_1), V1_16_5.const_cmp(&V1_16_1));
+}
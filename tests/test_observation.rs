@@ -0,0 +1,128 @@
+use seedcracker::item::Item;
+use seedcracker::joint::Clue;
+use seedcracker::observation::{ChestObservation, Container, LootTarget, Position};
+use seedcracker::pos::{BlockPos, ChunkPos};
+use seedcracker::random::mcversion::{V1_16_5, V1_18_2};
+use seedcracker::structure::Structure;
+use seedcracker::{target_loot, ItemStack};
+
+const SEED: u64 = -4872636734044769429_i64 as u64;
+
+const TREASURE: &str = "
+# Buried treasure, half of it transcribed
+chunk: -28 -73
+0: minecraft:leather_chestplate x1
+1: iron_ingot x2
+6: empty
+9: minecraft:diamond
+14: ?
+17: minecraft:emerald x6
+";
+
+#[test]
+fn test_parse() {
+    let observation = ChestObservation::parse(TREASURE).unwrap();
+    assert_eq!(observation.structure, Structure::BuriedTreasure);
+    assert_eq!(
+        observation.position,
+        Position::Chunk(ChunkPos::new(-28, -73))
+    );
+    assert_eq!(observation.container, Container::Chest);
+    assert_eq!(observation.target.known_slots(), 5);
+    assert_eq!(
+        observation.target.slots[1],
+        Some(ItemStack::new(Item::IronIngot, 2))
+    );
+    assert_eq!(observation.target.slots[14], None);
+    assert!(observation.target.matches(&target_loot()));
+    assert!(observation.to_clue().check(SEED));
+    assert!(!observation.to_clue().check(SEED + 1));
+
+    // The treasure is seeded the way the version it was seen in does.
+    let newer = ChestObservation::parse(&format!("{TREASURE}version: 1.18.2")).unwrap();
+    let Clue::BuriedTreasure { chest, .. } = newer.to_clue() else {
+        panic!("a buried treasure should give a buried treasure clue");
+    };
+    assert_eq!(chest.version, V1_18_2);
+    assert_eq!(chest.loot_table, observation.loot_table);
+
+    let village = ChestObservation::parse(
+        "structure: minecraft:village\nblock: 176 70 -20\nindex: 1\n\
+         loot_table: chests/village/village_weaponsmith\ncontainer: barrel",
+    )
+    .unwrap();
+    assert_eq!(village.chunk(), ChunkPos::new(11, -2));
+    assert_eq!(
+        village.position,
+        Position::Block(BlockPos::new(176, 70, -20))
+    );
+    assert_eq!(
+        village.loot_table.name,
+        "chests/village/village_weaponsmith"
+    );
+    assert!(matches!(village.to_clue(), Clue::Village(_)));
+
+    assert_eq!(village.version, V1_16_5);
+    let started =
+        ChestObservation::parse(&format!("{village}start: 0 -1\nversion: 1.18.2\n")).unwrap();
+    assert_eq!(started.version, V1_18_2);
+    assert!(started.to_string().contains("version: 1.18.2\n"));
+    assert_eq!(started.start_chunk, Some(ChunkPos::new(0, -1)));
+    assert_eq!(
+        ChestObservation::parse(&started.to_string()).unwrap(),
+        started
+    );
+    let Clue::Village(cracker) = started.to_clue() else {
+        panic!("a village chest should give a village clue");
+    };
+    assert_eq!(cracker.start_chunk, Some(ChunkPos::new(0, -1)));
+    assert_eq!(cracker.chest.version, V1_18_2);
+}
+
+#[test]
+fn test_exact_target() {
+    let target = LootTarget::exact(&target_loot());
+    assert_eq!(target.known_slots(), 27);
+    assert!(target.matches(&target_loot()));
+    let mut other = target_loot();
+    other.swap(0, 6);
+    assert!(!target.matches(&other));
+    assert!(LootTarget::unknown().matches(&other));
+
+    assert_eq!(target.count(Item::IronIngot), (8, true));
+    let observation = ChestObservation::parse(TREASURE).unwrap();
+    assert_eq!(observation.target.count(Item::IronIngot), (2, false));
+}
+
+#[test]
+fn test_errors() {
+    let error = |text: &str| ChestObservation::parse(text).unwrap_err();
+    assert_eq!(
+        error("chunk: 0 0\n3: minecraft:dimond x1"),
+        "line 2: unknown item `minecraft:dimond`"
+    );
+    assert_eq!(
+        error("chunk: 0 0\n\n3: minecraft:diamond x65"),
+        "line 3: 65 minecraft:diamond is more than a stack of 64"
+    );
+    assert_eq!(
+        error("chunk: 0 0\n3: minecraft:diamond x300"),
+        "line 2: 300 minecraft:diamond is more than a stack of 64"
+    );
+    assert_eq!(
+        error("chunk: 0 0\n3: minecraft:saddle x2"),
+        "line 2: 2 minecraft:saddle is more than a stack of 1"
+    );
+    assert_eq!(error("27: diamond"), "line 1: slot 27 is not in 0..27");
+    assert_eq!(error("1: diamond\n1: ?"), "line 2: slot 1 is listed twice");
+    assert_eq!(error("chunk: 1"), "line 1: expected 2 coordinates, got `1`");
+    assert_eq!(
+        error("chunk: 0 0\ncontainer: barrel"),
+        "a buried treasure is always a chest, not a minecraft:barrel"
+    );
+    assert_eq!(error("3: diamond"), "missing `chunk` or `block`");
+    assert_eq!(
+        error("chunk: 0 0\nstart: 0 0"),
+        "only a village has a `start`, not minecraft:buried_treasure"
+    );
+}
@@ -0,0 +1,30 @@
+use seedcracker::pos::{BlockPos, ChunkPos, RegionPos};
+
+#[test]
+fn test_block_to_chunk() {
+    assert_eq!(BlockPos::new(-1, 64, 15).to_chunk(), ChunkPos::new(-1, 0));
+    assert_eq!(BlockPos::new(-17, 0, 16).to_chunk(), ChunkPos::new(-2, 1));
+    assert_eq!(
+        ChunkPos::new(-28, -73).get_min_block(),
+        BlockPos::new(-448, 0, -1168)
+    );
+}
+
+#[test]
+fn test_java_wrapping() {
+    // `i << 4` on a Java `int` drops the high bits instead of overflowing.
+    assert_eq!(
+        ChunkPos::new(i32::MAX, 0x1000_0000).get_min_block(),
+        BlockPos::new(-16, 0, 0)
+    );
+    assert_eq!(
+        RegionPos::new(i32::MAX, 1).get_min_chunk(32),
+        ChunkPos::new(-32, 32)
+    );
+}
+
+#[test]
+fn test_chunk_to_region() {
+    assert_eq!(ChunkPos::new(-1, 31).to_region(32), RegionPos::new(-1, 0));
+    assert_eq!(ChunkPos::new(-33, 32).to_region(32), RegionPos::new(-2, 1));
+}
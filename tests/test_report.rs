@@ -0,0 +1,91 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use seedcracker::item::Item;
+use seedcracker::observation::ChestObservation;
+use seedcracker::pos::ChunkPos;
+use seedcracker::random::mcversion::V1_16_5;
+use seedcracker::report::{CrackReport, Observation, SeedResult};
+use seedcracker::structure::Structure;
+use seedcracker::{target_loot, ItemStack};
+
+fn report() -> CrackReport {
+    CrackReport {
+        observations: vec![
+            Observation::from(
+                &ChestObservation::parse(
+                    "block: -441 64 -1160\nversion: 1.17.1\n0: minecraft:leather_chestplate\n\
+                     6: empty\n17: minecraft:emerald x6\n",
+                )
+                .unwrap(),
+            ),
+            Observation::from(
+                &ChestObservation::parse("chunk: -28 -73\n13: minecraft:diamond\n").unwrap(),
+            ),
+        ],
+        backend: "cpu",
+        range: 0..1 << 36,
+        scanned: 1000,
+        started: UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+        elapsed: Duration::from_millis(2500),
+        results: vec![SeedResult {
+            structure_seed: 123,
+            world_seeds: vec![123, -4872636734044769429],
+        }],
+    }
+}
+
+#[test]
+fn test_report_json() {
+    assert_eq!(
+        report().to_json(),
+        concat!(
+            r#"{"schema":"seedcracker.crack/2","observations":["#,
+            r#"{"structure":"minecraft:buried_treasure","chunk":{"x":-28,"z":-73},"#,
+            r#""block":{"x":-441,"y":64,"z":-1160},"version":"1.17.1","#,
+            r#""loot_table":"chests/buried_treasure","#,
+            r#""items":[{"slot":0,"item":"minecraft:leather_chestplate","count":1},"#,
+            r#"{"slot":17,"item":"minecraft:emerald","count":6}],"empty":[6]},"#,
+            r#"{"structure":"minecraft:buried_treasure","chunk":{"x":-28,"z":-73},"#,
+            r#""block":null,"version":"1.16.5","loot_table":"chests/buried_treasure","#,
+            r#""items":[{"slot":13,"item":"minecraft:diamond","count":1}],"empty":[]}],"#,
+            r#""backend":"cpu","range":{"start":0,"end":68719476736,"scanned":1000},"#,
+            r#""timings":{"started_unix_ms":1700000000123,"elapsed_secs":2.500,"seeds_per_sec":400.0},"#,
+            r#""results":[{"structure_seed":123,"world_seeds":["123","-4872636734044769429"]}]}"#
+        )
+    );
+}
+
+#[test]
+fn test_report_json_lines() {
+    let report = report();
+    assert_eq!(
+        report.results[0].to_json_line(),
+        concat!(
+            r#"{"schema":"seedcracker.crack/2","type":"hit","structure_seed":123,"#,
+            r#""world_seeds":["123","-4872636734044769429"]}"#
+        )
+    );
+    let summary = report.to_json_line();
+    assert!(
+        summary.starts_with(r#"{"schema":"seedcracker.crack/2","type":"summary","observations":"#)
+    );
+    assert!(summary.ends_with(
+        r#""results":[{"structure_seed":123,"world_seeds":["123","-4872636734044769429"]}]}"#
+    ));
+}
+
+#[test]
+fn test_observation_from_inventory() {
+    let observation = Observation::from_inventory(
+        Structure::BuriedTreasure,
+        ChunkPos::new(-28, -73),
+        &target_loot(),
+    );
+    assert_eq!(observation.version, V1_16_5);
+    assert!(observation.slots.iter().all(Option::is_some));
+    assert_eq!(
+        observation.slots[0],
+        Some(ItemStack::new(Item::LeatherChestplate, 1))
+    );
+    assert!(observation.to_json().ends_with(r#""empty":[6]}"#));
+}
@@ -0,0 +1,139 @@
+use seedcracker::random::jrand::JRand;
+use seedcracker::random::reverse::{next_long_states, Call, ConstraintSolver, StateConstraint};
+
+const LARGE_BOUND: i32 = 1_000_000_007;
+/// Draws above `2^30` are all thrown away, so about half of the calls retry.
+const REJECTING_BOUND: i32 = (1 << 30) + 1;
+
+fn float_call(value: f32) -> Call {
+    Call::NextFloat {
+        min: value,
+        max: f32::from_bits(value.to_bits() + 1),
+    }
+}
+
+#[test]
+fn test_constraints() {
+    let constraint = StateConstraint::next_int(16, 5).unwrap();
+    assert_eq!(
+        constraint,
+        StateConstraint::Interval {
+            min: 5 << 44,
+            max: (6 << 44) - 1
+        }
+    );
+    assert_eq!(constraint.candidate_count(), 1 << 44);
+
+    let constraint = StateConstraint::next_int(REJECTING_BOUND, 3).unwrap();
+    assert_eq!(constraint.candidate_count(), 1 << 17);
+    assert!(constraint.matches(3 << 17));
+    assert!(!constraint.matches(((REJECTING_BOUND as i64) + 3) << 17));
+
+    let constraint = StateConstraint::next_float(0.5, 0.75).unwrap();
+    assert!(constraint.matches(1 << 47));
+    assert!(!constraint.matches(3 << 46));
+    assert_eq!(StateConstraint::next_int(10, 10), None);
+}
+
+#[test]
+fn test_solve() {
+    let seed = 8675309123456789;
+    let mut rand = JRand::new(seed);
+    let calls = vec![
+        Call::NextInt {
+            bound: 16,
+            value: rand.get_next_int_bound(16),
+        },
+        Call::Skip(2),
+        Call::NextInt {
+            bound: LARGE_BOUND,
+            value: {
+                rand.get_next_long();
+                rand.get_next_int_bound(LARGE_BOUND)
+            },
+        },
+        float_call(rand.get_next_float()),
+        Call::NextInt {
+            bound: 10,
+            value: rand.get_next_int_bound(10),
+        },
+    ];
+
+    let solver = ConstraintSolver::new(calls);
+    assert_eq!(
+        solver.solve_scrambled(0).unwrap(),
+        vec![seed & ((1 << 48) - 1)]
+    );
+}
+
+#[test]
+fn test_solve_rejected() {
+    // The first seed whose opening draw gets rejected exactly once.
+    let seed = (0..)
+        .find(|&seed| {
+            let mut rand = JRand::new(seed);
+            rand.next(31) > 1 << 30 && rand.next(31) <= 1 << 30
+        })
+        .unwrap();
+    let mut rand = JRand::new(seed);
+    let calls = vec![
+        Call::NextInt {
+            bound: REJECTING_BOUND,
+            value: rand.get_next_int_bound(REJECTING_BOUND),
+        },
+        float_call(rand.get_next_float()),
+        Call::NextIntUnknown { bound: 7 },
+        Call::NextInt {
+            bound: LARGE_BOUND,
+            value: {
+                rand.get_next_int_bound(7);
+                rand.get_next_int_bound(LARGE_BOUND)
+            },
+        },
+    ];
+
+    // Starting one draw later replays the same calls without the rejection.
+    let mut skipped = JRand::new(seed);
+    skipped.next(31);
+
+    let solver = ConstraintSolver::new(calls);
+    assert_eq!(solver.solve(0).unwrap(), vec![skipped.seed]);
+    let seeds = solver.solve(1).unwrap();
+    assert_eq!(seeds.len(), 2);
+    assert!(seeds.contains(&JRand::new(seed).seed));
+}
+
+#[test]
+fn test_solve_over_limit() {
+    let solver = ConstraintSolver::new(vec![Call::NextInt {
+        bound: 16,
+        value: 5,
+    }]);
+    assert!(solver.solve(0).is_err());
+    assert!(ConstraintSolver::new(vec![Call::Skip(3)]).solve(0).is_err());
+
+    let solver = ConstraintSolver::new(vec![Call::NextInt {
+        bound: REJECTING_BOUND,
+        value: 3,
+    }])
+    .with_candidate_limit(1 << 17);
+    assert!(solver.solve(0).is_ok());
+    assert!(solver.solve(1).is_err());
+}
+
+#[test]
+fn test_next_long_states() {
+    for seed in [0, 1, 42, -4872636734044769429, i64::MAX] {
+        let mut rand = JRand::new(seed);
+        let state = rand.next_seed();
+        let mut rand = JRand::new_scramble(state, false);
+        let value = rand.get_next_long();
+        assert!(next_long_states(value).contains(&state));
+        assert!(next_long_states(value)
+            .into_iter()
+            .all(|state| JRand::new_scramble(state, false).get_next_long() == value));
+    }
+    // `nextLong` only reaches one long in 65536.
+    let reachable = (0..64).filter(|&value| !next_long_states(value).is_empty());
+    assert!(reachable.count() < 8);
+}
@@ -0,0 +1,82 @@
+use std::thread;
+
+use seedcracker::pos::{BlockPos, RegionPos};
+use seedcracker::random::chunkrand::ChunkRand;
+use seedcracker::random::mcversion::V1_16_5;
+use seedcracker::random::mth::MASK_48;
+use seedcracker::random::seeds::{PopulationSeed, PositionSeed, RegionSeed};
+
+const WORLD_SEED: i64 = 4327857727348957239;
+
+#[test]
+fn test_base_stone_seed() {
+    const SEED: i64 = PositionSeed::get_base_stone_seed(WORLD_SEED, BlockPos::new(399, 45, 399));
+    assert_eq!(SEED, -1048058806902065195);
+}
+
+#[test]
+fn test_base_stone_seed_column() {
+    let mut seeds = [0; 16];
+    PositionSeed::fill_base_stone_seed_column(WORLD_SEED, BlockPos::new(399, 38, 399), &mut seeds);
+    for (y, &seed) in (38..).zip(seeds.iter()) {
+        assert_eq!(
+            seed,
+            PositionSeed::get_base_stone_seed(WORLD_SEED, BlockPos::new(399, y, 399))
+        );
+    }
+    assert_eq!(seeds[7], -1048058806902065195);
+}
+
+#[test]
+fn test_region_seed_to_world_seed() {
+    let region = RegionPos::new(55, -6);
+    let region_seed = ChunkRand::default().set_region_seed(WORLD_SEED, region, 53415);
+    assert_eq!(
+        RegionSeed::convert_to_world_seed(region_seed, region, 53415) & MASK_48,
+        WORLD_SEED & MASK_48
+    );
+}
+
+#[test]
+fn test_base_stone_seed_threads() {
+    let handles: Vec<_> = (0..8)
+        .map(|y| {
+            thread::spawn(move || {
+                PositionSeed::get_base_stone_seed(WORLD_SEED, BlockPos::new(399, 38 + y, 399))
+            })
+        })
+        .collect();
+    let seeds: Vec<i64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    // `new Random(worldSeed)` mixed with the coordinates in Java's wrapping `long`s.
+    assert_eq!(
+        seeds,
+        [
+            3523363730917712834,
+            1539816555036768423,
+            8806654045639544196,
+            6814099103601730153,
+            4848557392454760270,
+            -6331340325562364909,
+            -8314896297779862800,
+            -1048058806902065195,
+        ]
+    );
+}
+
+#[test]
+fn test_population_seed_reverse() {
+    let mut rand = ChunkRand::default().with_version(V1_16_5);
+    for (seed, block) in [
+        (WORLD_SEED, BlockPos::new(-448, 0, -1168)),
+        (-4872636734044769429, BlockPos::new(0, 0, 0)),
+        (12345, BlockPos::new(29_999_984, 0, -29_999_984)),
+    ] {
+        let structure_seed = seed & MASK_48;
+        let population_seed = rand.set_population_seed(structure_seed, block);
+        let seeds = PopulationSeed::reverse(population_seed, block);
+        assert!(seeds.contains(&structure_seed));
+        for seed in seeds {
+            assert_eq!(rand.set_population_seed(seed, block), population_seed);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use seedcracker::observation::LootTarget;
+use seedcracker::pos::ChunkPos;
+use seedcracker::simd::{check_seeds, LANES};
+use seedcracker::{check_seed, target_loot};
+
+const CHUNK: ChunkPos = ChunkPos::new(-28, -73);
+const TARGET: u64 = -4872636734044769429_i64 as u64;
+
+fn loot() -> LootTarget {
+    LootTarget::exact(&target_loot())
+}
+
+#[test]
+fn test_check_seeds() {
+    let mut seeds = [TARGET + 1; LANES];
+    seeds[5] = TARGET;
+    assert_eq!(check_seeds(&seeds, CHUNK, &loot()), 1 << 5);
+
+    let seeds = [TARGET, 0, TARGET, 7823457984237234584];
+    assert_eq!(check_seeds(&seeds, CHUNK, &loot()), 0b101);
+}
+
+#[test]
+fn test_matches_scalar() {
+    // Same lower 48 bits, so every seed gets through the spawn check and reaches the loot.
+    let lower48 = TARGET & ((1 << 48) - 1);
+    for high in (0..0x10000_u64).step_by(LANES) {
+        let seeds: [u64; LANES] = std::array::from_fn(|i| lower48 | (high + i as u64) << 48);
+        let expected = seeds
+            .iter()
+            .enumerate()
+            .filter(|(_, &seed)| check_seed(seed, CHUNK, &loot()))
+            .fold(0, |mask, (i, _)| mask | 1 << i);
+        assert_eq!(check_seeds(&seeds, CHUNK, &loot()), expected);
+    }
+}
+
+#[test]
+fn test_random_seeds() {
+    for start in (0..1 << 16).step_by(LANES) {
+        let seeds: [u64; LANES] =
+            std::array::from_fn(|i| (start + i as u64).wrapping_mul(0x9e3779b97f4a7c15));
+        let mask = check_seeds(&seeds, CHUNK, &loot());
+        for (i, &seed) in seeds.iter().enumerate() {
+            assert_eq!(mask >> i & 1 != 0, check_seed(seed, CHUNK, &loot()));
+        }
+    }
+}
+
+#[test]
+fn test_crack_window() {
+    // Cracks the seed back out of a window around it, batch by batch like a scanner.
+    let window = TARGET - 100_000..TARGET + 100_000;
+    let mut hits = Vec::new();
+    for start in window.step_by(LANES) {
+        let seeds: [u64; LANES] = std::array::from_fn(|i| start + i as u64);
+        let mut mask = check_seeds(&seeds, CHUNK, &loot());
+        while mask != 0 {
+            hits.push(seeds[mask.trailing_zeros() as usize]);
+            mask &= mask - 1;
+        }
+    }
+    assert_eq!(hits, vec![TARGET]);
+}
+
+#[test]
+fn test_partial_target() {
+    // With unknown slots the first pool's totals are only lower bounds.
+    let mut partial = loot();
+    partial.slots[..20].fill(None);
+    let mut seeds = [TARGET + 1; LANES];
+    seeds[2] = TARGET;
+    let mask = check_seeds(&seeds, CHUNK, &partial);
+    for (i, &seed) in seeds.iter().enumerate() {
+        assert_eq!(mask >> i & 1 != 0, check_seed(seed, CHUNK, &partial));
+    }
+    assert_eq!(mask & 1 << 2, 1 << 2);
+}
@@ -0,0 +1,100 @@
+use seedcracker::pos::ChunkPos;
+use seedcracker::slime::{is_slime_chunk, SlimeChunk, SlimeCracker, LOW_BITS};
+
+const WORLD_SEED: i64 = 8675309123456789;
+
+const SLIME_CHUNKS: [(i32, i32); 24] = [
+    (-20, -6),
+    (-20, -4),
+    (-20, -1),
+    (-20, 2),
+    (-19, -16),
+    (-19, -15),
+    (-19, 18),
+    (-18, 7),
+    (-18, 14),
+    (-17, -16),
+    (-17, -5),
+    (-17, -1),
+    (-17, 5),
+    (-16, 1),
+    (-15, -10),
+    (-15, -6),
+    (-14, -6),
+    (-14, -1),
+    (-14, 0),
+    (-14, 1),
+    (-14, 11),
+    (-13, -20),
+    (-13, -12),
+    (-13, -9),
+];
+
+const NON_SLIME_CHUNKS: [(i32, i32); 8] = [
+    (-20, -15),
+    (-20, -8),
+    (-20, 6),
+    (-20, 13),
+    (-19, -9),
+    (-19, -2),
+    (-19, 5),
+    (-19, 12),
+];
+
+fn observations() -> Vec<SlimeChunk> {
+    SLIME_CHUNKS
+        .iter()
+        .map(|&(x, z)| SlimeChunk::new(ChunkPos::new(x, z), true))
+        .chain(
+            NON_SLIME_CHUNKS
+                .iter()
+                .map(|&(x, z)| SlimeChunk::new(ChunkPos::new(x, z), false)),
+        )
+        .collect()
+}
+
+#[test]
+fn test_is_slime_chunk() {
+    assert!(is_slime_chunk(WORLD_SEED, ChunkPos::new(0, 0)));
+    assert!(!is_slime_chunk(WORLD_SEED, ChunkPos::new(-3, 5)));
+    for observation in observations() {
+        assert!(observation.matches(WORLD_SEED));
+    }
+}
+
+#[test]
+fn test_low_bits_candidates() {
+    let cracker = SlimeCracker::new(&observations());
+    let candidates = cracker.low_bits_candidates();
+    let low_bits = WORLD_SEED & ((1 << LOW_BITS) - 1);
+    assert!(candidates.contains(&low_bits));
+    assert!(candidates.len() < 1 << 8);
+}
+
+#[test]
+fn test_low_bits_far_out() {
+    // The `int` products of these coordinates wrap, as they do in game.
+    let far: Vec<SlimeChunk> = (0..40)
+        .map(|i| ChunkPos::new(1_874_999 - i * 7, -1_874_999 + i * 13))
+        .map(|chunk| SlimeChunk::new(chunk, is_slime_chunk(WORLD_SEED, chunk)))
+        .collect();
+    assert!(far.iter().any(|observation| observation.is_slime));
+    let candidates = SlimeCracker::new(&far).low_bits_candidates();
+    assert!(candidates.contains(&(WORLD_SEED & ((1 << LOW_BITS) - 1))));
+}
+
+#[test]
+fn test_check_structure_seed() {
+    let cracker = SlimeCracker::new(&observations());
+    assert!(cracker.check(WORLD_SEED as u64));
+    assert!(cracker.check((WORLD_SEED as u64 & ((1 << 48) - 1)) | (0xabcd << 48)));
+    assert!(!cracker.check(WORLD_SEED as u64 + (1 << LOW_BITS)));
+}
+
+#[test]
+fn test_crack_window() {
+    let cracker = SlimeCracker::new(&observations());
+    let structure_seed = WORLD_SEED as u64 & ((1 << 48) - 1);
+    let window = structure_seed - (1 << 29)..structure_seed + (1 << 29);
+    assert_eq!(cracker.crack_in(window), vec![structure_seed]);
+}
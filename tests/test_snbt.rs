@@ -0,0 +1,127 @@
+use seedcracker::item::Item;
+use seedcracker::loot::tables::VILLAGE_WEAPONSMITH;
+use seedcracker::nbt::snbt::parse;
+use seedcracker::nbt::Tag;
+use seedcracker::observation::{ChestObservation, Container, LootTarget, Position};
+use seedcracker::pos::{BlockPos, ChunkPos};
+use seedcracker::structure::Structure;
+use seedcracker::ItemStack;
+
+const DATA_GET: &str = "[12:01:44] [Render thread/INFO]: [CHAT] -440, 62, -1160 has the \
+    following block data: [{Slot: 0b, id: \"minecraft:leather_chestplate\", Count: 1b, \
+    tag: {Damage: 0}}, {Slot: 1b, id: \"minecraft:iron_ingot\", Count: 2b}, \
+    {Slot: 13b, id: \"minecraft:diamond\", Count: 1b}]";
+
+#[test]
+fn test_parse() {
+    let tag =
+        parse(r#"{a: 1b, "b c": [I; 1, -2], d: [1.5d, 2e3], e: 'it\'s', f: true, g: 3L, h: {}}"#)
+            .unwrap();
+    assert_eq!(tag.get("a"), Some(&Tag::Byte(1)));
+    assert_eq!(tag.get("b c"), Some(&Tag::IntArray(vec![1, -2])));
+    assert_eq!(
+        tag.get("d"),
+        Some(&Tag::List(vec![Tag::Double(1.5), Tag::Double(2000.0)]))
+    );
+    assert_eq!(tag.get("e").and_then(Tag::as_str), Some("it's"));
+    assert_eq!(tag.get("f"), Some(&Tag::Byte(1)));
+    assert_eq!(tag.get("g").and_then(Tag::as_i64), Some(3));
+    assert_eq!(tag.get("h"), Some(&Tag::Compound(Vec::new())));
+    assert_eq!(parse("minecraft"), Ok(Tag::String("minecraft".to_string())));
+
+    assert_eq!(
+        parse("{a: 1"),
+        Err("expected `,` or `}` at column 6".to_string())
+    );
+    assert_eq!(
+        parse("[1] x"),
+        Err("trailing characters at column 5".to_string())
+    );
+    assert!(parse("\"open").is_err());
+}
+
+#[test]
+fn test_block_data() {
+    let observation =
+        ChestObservation::from_block_data(DATA_GET, None, Structure::BuriedTreasure).unwrap();
+    assert_eq!(
+        observation.position,
+        Position::Block(BlockPos::new(-440, 62, -1160))
+    );
+    assert_eq!(observation.chunk(), ChunkPos::new(-28, -73));
+    assert_eq!(observation.target.known_slots(), 27);
+    assert_eq!(
+        observation.target.slots[1],
+        Some(ItemStack::new(Item::IronIngot, 2))
+    );
+    assert!(observation.target.slots[2].is_some_and(|stack| stack.is_empty()));
+
+    // 1.20.5+ spells it `count` and may leave it out for a single item.
+    let items =
+        "[{Slot: 4b, id: \"minecraft:emerald\", count: 6}, {Slot: 5b, id: \"minecraft:tnt\"}]";
+    let block = BlockPos::new(9, 60, 25);
+    let observation =
+        ChestObservation::from_block_data(items, Some(block), Structure::BuriedTreasure).unwrap();
+    assert_eq!(observation.position, Position::Block(block));
+    assert_eq!(
+        observation.target.slots[4],
+        Some(ItemStack::new(Item::Emerald, 6))
+    );
+    assert_eq!(
+        observation.target.slots[5],
+        Some(ItemStack::new(Item::TNT, 1))
+    );
+
+    // The whole block data of an unopened chest names its table and the seed its loot
+    // will be rolled from, without any `Items`.
+    let data = "{x: 9, y: 60, z: 25, id: \"minecraft:barrel\", \
+                LootTable: \"minecraft:chests/village/village_weaponsmith\", \
+                LootTableSeed: -4872636734044769429L}";
+    let observation =
+        ChestObservation::from_block_data(data, Some(block), Structure::Village).unwrap();
+    assert_eq!(observation.structure, Structure::Village);
+    assert_eq!(observation.container, Container::Barrel);
+    assert_eq!(
+        observation.loot_table.name,
+        "chests/village/village_weaponsmith"
+    );
+    assert_eq!(
+        observation.target,
+        LootTarget::exact(&VILLAGE_WEAPONSMITH.get_loot(-4872636734044769429, true))
+    );
+    assert_eq!(observation.loot_seed, Some(-4872636734044769429));
+}
+
+#[test]
+fn test_block_data_errors() {
+    let error = |text: &str| {
+        ChestObservation::from_block_data(text, None, Structure::BuriedTreasure).unwrap_err()
+    };
+    assert_eq!(error("[]"), "no block coordinates given");
+    assert_eq!(
+        error("1, 2, 3 has the following block data: [{Slot: 0b, id: \"minecraft:stone_axe\", Count: 1b}]"),
+        "slot 0: unknown item `minecraft:stone_axe`"
+    );
+    assert_eq!(
+        error("1, 2, 3 has the following block data: [{Slot: 30b, id: \"minecraft:diamond\"}]"),
+        "slot 30 is not in 0..27"
+    );
+    assert_eq!(
+        error("1, 2, 3 has the following block data: {LootTable: \"minecraft:chests/simple_dungeon\", LootTableSeed: 5L}"),
+        "minecraft:buried_treasure has no loot table `minecraft:chests/simple_dungeon`"
+    );
+    assert_eq!(
+        error("1, 2, 3 has the following block data: {LootTable: \"minecraft:chests/buried_treasure\", LootTableSeed: 0L}"),
+        "the unopened chest has no `LootTableSeed` to roll its loot from"
+    );
+    assert_eq!(
+        error("1, 2, 3 has the following block data: {id: \"minecraft:chest\"}"),
+        "the block has no `Items`"
+    );
+
+    let deep = format!("{}{}", "[".repeat(600), "]".repeat(600));
+    assert!(parse(&deep)
+        .unwrap_err()
+        .starts_with("NBT nested too deeply"));
+    assert!(parse(&format!("{}{}", "[".repeat(500), "]".repeat(500))).is_ok());
+}
@@ -0,0 +1,69 @@
+use seedcracker::pos::ChunkPos;
+use seedcracker::random::mth::MASK_48;
+use seedcracker::slime::{is_slime_chunk, SlimeChunk, SlimeCracker};
+use seedcracker::text_seed::{
+    find_text_world_seeds_in, hash_reachable_world_seeds, hash_world_seed_from_structure_seed,
+    is_hash_reachable, java_hash_code, text_to_world_seed,
+};
+
+#[test]
+fn test_java_hash_code() {
+    assert_eq!(java_hash_code("minecraft"), 695073197);
+    assert_eq!(java_hash_code("Glacier"), 1772835215);
+    assert_eq!(java_hash_code("héllo wörld 🌍"), 1121588984);
+    assert_eq!(java_hash_code(""), 0);
+}
+
+#[test]
+fn test_text_to_world_seed() {
+    assert_eq!(text_to_world_seed("Glacier"), Some(1772835215));
+    assert_eq!(
+        text_to_world_seed("-4872636734044769429"),
+        Some(-4872636734044769429)
+    );
+    assert_eq!(text_to_world_seed(" Glacier\t"), Some(1772835215));
+    assert_eq!(text_to_world_seed(" 42 "), Some(42));
+    assert_eq!(text_to_world_seed(""), None);
+    assert_eq!(text_to_world_seed("   "), None);
+
+    // `String.trim` drops every control character but no Unicode space.
+    assert_eq!(text_to_world_seed("\u{1}Glacier\u{1f}"), Some(1772835215));
+    assert_eq!(text_to_world_seed("\u{0}\r\n"), None);
+    assert_eq!(
+        text_to_world_seed("\u{a0}Glacier"),
+        Some(java_hash_code("\u{a0}Glacier") as i64)
+    );
+    assert_eq!(
+        text_to_world_seed("\u{3000}"),
+        Some(java_hash_code("\u{3000}") as i64)
+    );
+}
+
+#[test]
+fn test_hash_reachable() {
+    assert!(is_hash_reachable(-1772835215));
+    assert!(!is_hash_reachable(8675309123456789));
+    assert_eq!(
+        hash_world_seed_from_structure_seed((-1772835215_i64 & MASK_48) as u64),
+        Some(-1772835215)
+    );
+    assert_eq!(hash_world_seed_from_structure_seed(1 << 40), None);
+    assert_eq!(hash_reachable_world_seeds().size_hint().0, 1 << 32);
+}
+
+#[test]
+fn test_crack_text_seed() {
+    let world_seed = text_to_world_seed("Glacier").unwrap();
+    let observations: Vec<SlimeChunk> = (0..256)
+        .map(|i| {
+            let chunk = ChunkPos::new(i % 16 * 7 - 56, i / 16 * 11 - 88);
+            SlimeChunk::new(chunk, is_slime_chunk(world_seed, chunk))
+        })
+        .collect();
+    let cracker = SlimeCracker::new(&observations);
+    let hash = world_seed as i32;
+    let found = find_text_world_seeds_in(hash - (1 << 20)..=hash + (1 << 20), |seed| {
+        cracker.check(seed)
+    });
+    assert_eq!(found, [world_seed]);
+}
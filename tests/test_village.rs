@@ -0,0 +1,72 @@
+use seedcracker::loot::tables::{VILLAGE_FISHER, VILLAGE_PLAINS_HOUSE, VILLAGE_WEAPONSMITH};
+use seedcracker::observation::LootTarget;
+use seedcracker::pos::{ChunkPos, RegionPos};
+use seedcracker::structure::{Structure, StructureChest};
+use seedcracker::village::{can_spawn_village, get_village_chunk, VillageCracker};
+
+const STRUCTURE_SEED: i64 = 8675309123456789;
+const CHEST: ChunkPos = ChunkPos::new(11, 9);
+
+#[test]
+fn test_village_chunk() {
+    assert_eq!(
+        get_village_chunk(STRUCTURE_SEED, RegionPos::new(0, 0)),
+        ChunkPos::new(10, 10)
+    );
+    assert_eq!(
+        get_village_chunk(STRUCTURE_SEED, RegionPos::new(-3, 5)),
+        ChunkPos::new(-87, 162)
+    );
+    assert!(can_spawn_village(STRUCTURE_SEED, ChunkPos::new(-87, 162)));
+    assert!(!can_spawn_village(STRUCTURE_SEED, ChunkPos::new(-86, 162)));
+}
+
+#[test]
+fn test_village_chest() {
+    let chest =
+        StructureChest::new(Structure::Village, CHEST, 0).with_loot_table(&VILLAGE_WEAPONSMITH);
+    let target = LootTarget::exact(&chest.get_loot(STRUCTURE_SEED, true));
+    let cracker = VillageCracker::new(
+        Some(ChunkPos::new(10, 10)),
+        CHEST,
+        0,
+        &VILLAGE_WEAPONSMITH,
+        target,
+    );
+    assert!(cracker.check(STRUCTURE_SEED as u64));
+    assert!(!cracker.check(STRUCTURE_SEED as u64 + 1));
+
+    let cracker = VillageCracker::new(
+        Some(ChunkPos::new(11, 10)),
+        CHEST,
+        0,
+        &VILLAGE_WEAPONSMITH,
+        target,
+    );
+    assert!(!cracker.check(STRUCTURE_SEED as u64));
+
+    let cracker = VillageCracker::new(None, CHEST, 0, &VILLAGE_PLAINS_HOUSE, target);
+    assert!(!cracker.check(STRUCTURE_SEED as u64));
+
+    // A chest seen only in part still narrows the seed down.
+    let mut partial = target;
+    partial.slots[..20].fill(None);
+    let cracker = VillageCracker::new(
+        Some(ChunkPos::new(10, 10)),
+        CHEST,
+        0,
+        &VILLAGE_WEAPONSMITH,
+        partial,
+    );
+    assert!(cracker.check(STRUCTURE_SEED as u64));
+}
+
+#[test]
+fn test_stack_splitting() {
+    for seed in 0..512 {
+        let loot = VILLAGE_FISHER.get_loot(seed, true);
+        assert!(loot
+            .iter()
+            .all(|stack| stack.get_count() <= stack.get_item().max_stack_size()));
+    }
+}
@@ -0,0 +1,54 @@
+use seedcracker::pos::BlockPos;
+use seedcracker::random::xoroshiro::{mix_stafford_13, Xoroshiro, XoroshiroPositional};
+
+#[test]
+fn test_next_values() {
+    let mut rand = Xoroshiro::new(8675309123456789);
+    assert_eq!(rand.get_next_long(), -2394001656350983597);
+    assert_eq!(rand.get_next_long(), 973849201020658331);
+    assert_eq!(rand.get_next_int_bound(100), 14);
+    assert_eq!(rand.get_next_int_bound(7), 1);
+    assert!((rand.get_next_float() - 0.5162799).abs() < f32::EPSILON);
+    assert!((rand.get_next_double() - 0.39096046122466344).abs() < f64::EPSILON);
+    assert!(!rand.get_next_bool());
+}
+
+#[test]
+fn test_zero_seed() {
+    let mut rand = Xoroshiro::from_seed_128(0, 0);
+    assert_eq!(rand.get_next_long(), 6807859099481836695);
+}
+
+#[test]
+fn test_positional() {
+    let factory: XoroshiroPositional = Xoroshiro::new(42).fork_positional();
+    assert_eq!(
+        factory.at(BlockPos::new(12, -64, -7)).get_next_long(),
+        8169336247386852754
+    );
+    assert_eq!(
+        factory.from_hash_of("minecraft:offset").get_next_long(),
+        2458873966747037180
+    );
+}
+
+/// Values from the reference SplitMix64 and xoroshiro128++ implementations, which share
+/// nothing with this crate but the algorithm.
+#[test]
+fn test_reference_vectors() {
+    let gamma = 0x9e3779b97f4a7c15_u64;
+    assert_eq!(mix_stafford_13(gamma as i64) as u64, 0xe220a8397b1dcdaf);
+    assert_eq!(
+        mix_stafford_13(gamma.wrapping_mul(2) as i64) as u64,
+        0x6e789e6aa1b965f4
+    );
+    assert_eq!(
+        mix_stafford_13(gamma.wrapping_mul(3) as i64) as u64,
+        0x06c45d188009454f
+    );
+
+    let mut rand = Xoroshiro::from_seed_128(1, 2);
+    assert_eq!(rand.get_next_long(), 393217);
+    assert_eq!(rand.get_next_long(), 669327710093319);
+    assert_eq!(rand.get_next_long(), 1732421326133921491);
+}